@@ -0,0 +1,862 @@
+//! A language server that exposes the IDE capabilities of `typst::ide` to
+//! editors over stdio.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    DidSaveTextDocument, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, DocumentSymbolRequest, FoldingRangeRequest, GotoDefinition, HoverRequest,
+    References, Rename, Request as _, SemanticTokensFullRequest, SignatureHelpRequest,
+};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams,
+    CompletionResponse, CompletionTextEdit, CompletionTriggerKind,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, DocumentSymbol, DocumentSymbolParams,
+    DocumentSymbolResponse, Documentation, FoldingRange, FoldingRangeKind,
+    FoldingRangeParams, FoldingRangeProviderCapability, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, HoverProviderCapability,
+    InitializeParams, InsertTextFormat, Location, MarkupContent, MarkupKind, OneOf,
+    ParameterInformation, ParameterLabel, Position, PublishDiagnosticsParams,
+    ReferenceParams, RenameParams, SemanticToken, SemanticTokenType, SemanticTokens,
+    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
+    SemanticTokensParams, SemanticTokensResult, SemanticTokensServerCapabilities,
+    ServerCapabilities, SignatureHelp, SignatureHelpOptions, SignatureHelpParams,
+    SignatureInformation, TextDocumentIdentifier, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Url, WorkspaceEdit,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use typst::diag::{Severity, SourceError, StrResult};
+use typst::doc::Document;
+use typst::ide::{
+    highlight, Completion as TypstCompletion, CompletionKind, FoldKind, Symbol,
+    SymbolKind, Tag, Tooltip,
+};
+use typst::syntax::{is_newline, LinkedNode, Source, SourceId};
+use typst::World;
+
+use super::{FontSearcher, LspCommand, SystemWorld};
+
+/// Run the language server until the client asks it to shut down.
+pub fn run(command: LspCommand) -> StrResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(capabilities()).unwrap();
+    let params = connection
+        .initialize(capabilities)
+        .map_err(|_| "failed to initialize language server")?;
+    let params: InitializeParams = serde_json::from_value(params)
+        .map_err(|_| "received invalid initialization parameters")?;
+
+    // Prefer the explicitly configured root over the editor's workspace.
+    let root = command
+        .root
+        .or_else(|| params.root_uri.and_then(|uri| uri.to_file_path().ok()))
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let searcher = FontSearcher::system(&command.font_paths);
+    let mut world = SystemWorld::new(root, command.inputs, searcher);
+    world.max_iterations = command.max_iterations;
+
+    let main = match command.main {
+        Some(path) => Some(path.canonicalize().map_err(|_| "main file not found")?),
+        None => None,
+    };
+
+    let mut server = Server {
+        connection,
+        world,
+        main,
+        last: None,
+        documents: HashMap::new(),
+        document: Document::default(),
+        published: HashSet::new(),
+    };
+
+    server.main_loop()?;
+    drop(server);
+    io_threads.join().map_err(|_| "failed to shut down language server")?;
+    Ok(())
+}
+
+/// The capabilities the server announces to the client.
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::INCREMENTAL,
+        )),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(
+                ["#", ".", "@", "<", "(", ":"].map(String::from).to_vec(),
+            ),
+            ..Default::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Left(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(["(", ","].map(String::from).to_vec()),
+            retrigger_characters: Some([":"].map(String::from).to_vec()),
+            ..Default::default()
+        }),
+        semantic_tokens_provider: Some(
+            SemanticTokensServerCapabilities::SemanticTokensOptions(
+                SemanticTokensOptions {
+                    legend: SemanticTokensLegend {
+                        token_types: Tag::LIST
+                            .iter()
+                            .map(|&tag| token_type(tag))
+                            .collect(),
+                        token_modifiers: vec![],
+                    },
+                    full: Some(SemanticTokensFullOptions::Bool(true)),
+                    ..Default::default()
+                },
+            ),
+        ),
+        ..Default::default()
+    }
+}
+
+/// A custom request that renders the document to SVG pages.
+enum PreviewRequest {}
+
+impl lsp_types::request::Request for PreviewRequest {
+    type Params = PreviewParams;
+    type Result = Option<PreviewResult>;
+    const METHOD: &'static str = "typst/preview";
+}
+
+/// The parameters of a preview request.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreviewParams {
+    /// The document to compile and render.
+    text_document: TextDocumentIdentifier,
+}
+
+/// The response to a preview request.
+#[derive(Debug, Deserialize, Serialize)]
+struct PreviewResult {
+    /// One SVG image per page.
+    pages: Vec<String>,
+}
+
+/// The state of the language server.
+struct Server {
+    connection: Connection,
+    world: SystemWorld,
+    /// The file configured with `--main`, if any.
+    main: Option<PathBuf>,
+    /// The most recently opened or edited file.
+    last: Option<Url>,
+    /// The files that are currently open in the editor.
+    documents: HashMap<Url, SourceId>,
+    /// The most recent successfully compiled document.
+    document: Document,
+    /// The files for which diagnostics were last published.
+    published: HashSet<Url>,
+}
+
+impl Server {
+    /// Handle messages until the client shuts the server down.
+    fn main_loop(&mut self) -> StrResult<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(req) => {
+                    let shutdown = self
+                        .connection
+                        .handle_shutdown(&req)
+                        .map_err(|_| "failed to shut down language server")?;
+                    if shutdown {
+                        return Ok(());
+                    }
+
+                    let response = self.handle_request(req);
+                    self.send(response.into());
+                }
+                Message::Notification(notification) => {
+                    self.handle_notification(notification);
+                }
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Answer a request from the client.
+    fn handle_request(&mut self, req: Request) -> Response {
+        match req.method.as_str() {
+            Completion::METHOD => respond(req, |params| self.completion(params)),
+            HoverRequest::METHOD => respond(req, |params| self.hover(params)),
+            GotoDefinition::METHOD => respond(req, |params| self.definition(params)),
+            References::METHOD => respond(req, |params| self.references(params)),
+            Rename::METHOD => try_respond(req, |params| self.rename(params)),
+            DocumentSymbolRequest::METHOD => {
+                respond(req, |params| self.document_symbols(params))
+            }
+            FoldingRangeRequest::METHOD => respond(req, |params| self.folding(params)),
+            SignatureHelpRequest::METHOD => {
+                respond(req, |params| self.signature_help(params))
+            }
+            SemanticTokensFullRequest::METHOD => {
+                respond(req, |params| self.semantic_tokens(params))
+            }
+            PreviewRequest::METHOD => respond(req, |params| self.preview(params)),
+            _ => Response::new_err(
+                req.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request `{}`", req.method),
+            ),
+        }
+    }
+
+    /// React to a notification from the client.
+    fn handle_notification(&mut self, notification: Notification) {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                notify(notification, |params| self.open(params))
+            }
+            DidChangeTextDocument::METHOD => {
+                notify(notification, |params| self.change(params))
+            }
+            DidSaveTextDocument::METHOD => {
+                notify(notification, |params| self.save(params))
+            }
+            DidCloseTextDocument::METHOD => {
+                notify(notification, |params| self.close(params))
+            }
+            _ => {}
+        }
+    }
+
+    /// A file was opened in the editor.
+    fn open(&mut self, params: DidOpenTextDocumentParams) {
+        let uri = params.text_document.uri;
+        let Ok(path) = uri.to_file_path() else { return };
+        self.reset();
+        let Ok(id) = self.world.update(&path, params.text_document.text) else { return };
+        self.documents.insert(uri.clone(), id);
+        self.last = Some(uri);
+        self.compile();
+    }
+
+    /// A file was edited in the editor.
+    fn change(&mut self, params: DidChangeTextDocumentParams) {
+        let uri = params.text_document.uri;
+        let Some(id) = self.id(&uri) else { return };
+        for change in params.content_changes {
+            let source = self.world.source(id);
+            let range = match change.range {
+                Some(range) => {
+                    let Some(start) = to_offset(source, range.start) else { continue };
+                    let Some(end) = to_offset(source, range.end) else { continue };
+                    start..end
+                }
+                None => 0..source.len_bytes(),
+            };
+            self.world.edit(id, range, &change.text);
+        }
+
+        self.last = Some(uri);
+        self.compile();
+    }
+
+    /// A file was saved. Files that aren't open in the editor may have
+    /// changed on disk as well, so we reload them before recompiling.
+    fn save(&mut self, _: DidSaveTextDocumentParams) {
+        self.reset();
+        self.compile();
+    }
+
+    /// A file was closed in the editor. From now on, its contents on disk
+    /// are used instead of the editor buffer.
+    fn close(&mut self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.remove(&uri);
+        if self.last.as_ref() == Some(&uri) {
+            self.last = None;
+        }
+
+        self.reset();
+        self.compile();
+    }
+
+    /// Complete the code at the cursor.
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let source = self.world.source(self.id(&position.text_document.uri)?);
+        let cursor = to_offset(source, position.position)?;
+        let explicit = params
+            .context
+            .map_or(false, |ctx| ctx.trigger_kind == CompletionTriggerKind::INVOKED);
+
+        let (from, completions) = typst::ide::autocomplete(
+            &self.world,
+            &self.document.pages,
+            source,
+            cursor,
+            explicit,
+        )?;
+
+        let range = lsp_types::Range::new(to_position(source, from), position.position);
+        Some(CompletionResponse::Array(
+            completions
+                .into_iter()
+                .map(|completion| completion_item(completion, range))
+                .collect(),
+        ))
+    }
+
+    /// Describe the item under the cursor.
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let source = self.world.source(self.id(&position.text_document.uri)?);
+        let cursor = to_offset(source, position.position)?;
+        let tooltip =
+            typst::ide::tooltip(&self.world, &self.document.pages, source, cursor)?;
+
+        let value = match tooltip {
+            Tooltip::Text(text) => text.into(),
+            Tooltip::Code(code) => format!("```typc\n{code}\n```"),
+        };
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        })
+    }
+
+    /// Find where the item under the cursor is defined.
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let source = self.world.source(self.id(&position.text_document.uri)?);
+        let cursor = to_offset(source, position.position)?;
+        let definition = typst::ide::definition(&self.world, source, cursor)?;
+        let location = self.location(definition.source(), definition.range())?;
+        Some(GotoDefinitionResponse::Scalar(location))
+    }
+
+    /// Find all references to the item under the cursor.
+    fn references(&self, params: ReferenceParams) -> Option<Vec<Location>> {
+        let position = params.text_document_position;
+        let source = self.world.source(self.id(&position.text_document.uri)?);
+        let cursor = to_offset(source, position.position)?;
+
+        // The client decides whether the definition itself counts.
+        let definition = typst::ide::definition(&self.world, source, cursor);
+        let references = typst::ide::references(&self.world, source, cursor);
+        Some(
+            references
+                .into_iter()
+                .filter(|(id, range)| {
+                    params.context.include_declaration
+                        || definition.as_ref().map_or(true, |definition| {
+                            definition.source() != *id || definition.range() != *range
+                        })
+                })
+                .filter_map(|(id, range)| self.location(id, range))
+                .collect(),
+        )
+    }
+
+    /// Rename the item under the cursor across all files.
+    fn rename(&self, params: RenameParams) -> StrResult<Option<WorkspaceEdit>> {
+        let position = params.text_document_position;
+        let Some(id) = self.id(&position.text_document.uri) else { return Ok(None) };
+        let source = self.world.source(id);
+        let Some(cursor) = to_offset(source, position.position) else { return Ok(None) };
+
+        let mut changes = HashMap::<Url, Vec<TextEdit>>::new();
+        for (id, range, text) in
+            typst::ide::rename(&self.world, source, cursor, &params.new_name)?
+        {
+            let Some(location) = self.location(id, range) else { continue };
+            changes
+                .entry(location.uri)
+                .or_default()
+                .push(TextEdit::new(location.range, text.into()));
+        }
+
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
+
+    /// Show the signature of the function whose arguments are being typed.
+    fn signature_help(&self, params: SignatureHelpParams) -> Option<SignatureHelp> {
+        let position = params.text_document_position_params;
+        let source = self.world.source(self.id(&position.text_document.uri)?);
+        let cursor = to_offset(source, position.position)?;
+        let help = typst::ide::signature_help(&self.world, source, cursor)?;
+
+        let utf16 = |offset: usize| help.label[..offset].encode_utf16().count() as u32;
+        let parameters = help
+            .params
+            .iter()
+            .map(|param| {
+                let mut docs = String::from(param.docs.as_str());
+                if let Some(default) = &param.default {
+                    write!(docs, "\n\nDefault: `{default}`").unwrap();
+                }
+
+                ParameterInformation {
+                    label: ParameterLabel::LabelOffsets([
+                        utf16(param.range.start),
+                        utf16(param.range.end),
+                    ]),
+                    documentation: Some(markdown(docs)),
+                }
+            })
+            .collect();
+
+        Some(SignatureHelp {
+            signatures: vec![SignatureInformation {
+                label: help.label.to_string(),
+                documentation: Some(markdown(help.docs.into())),
+                parameters: Some(parameters),
+                active_parameter: help.active.map(|i| i as u32),
+            }],
+            active_signature: Some(0),
+            active_parameter: help.active.map(|i| i as u32),
+        })
+    }
+
+    /// Outline the structure of a file.
+    fn document_symbols(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Option<DocumentSymbolResponse> {
+        let source = self.world.source(self.id(&params.text_document.uri)?);
+        let symbols = typst::ide::symbols(source);
+        Some(DocumentSymbolResponse::Nested(
+            symbols
+                .into_iter()
+                .map(|symbol| document_symbol(source, symbol))
+                .collect(),
+        ))
+    }
+
+    /// Find the foldable ranges in a file.
+    fn folding(&self, params: FoldingRangeParams) -> Option<Vec<FoldingRange>> {
+        let source = self.world.source(self.id(&params.text_document.uri)?);
+        Some(
+            typst::ide::folding(source)
+                .into_iter()
+                .map(|fold| {
+                    let range = to_range(source, fold.range);
+                    FoldingRange {
+                        start_line: range.start.line,
+                        start_character: Some(range.start.character),
+                        end_line: range.end.line,
+                        end_character: Some(range.end.character),
+                        kind: match fold.kind {
+                            FoldKind::Section => Some(FoldingRangeKind::Region),
+                            FoldKind::Block | FoldKind::Raw => None,
+                        },
+                        collapsed_text: None,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Classify all tokens in a file for syntax highlighting.
+    fn semantic_tokens(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Option<SemanticTokensResult> {
+        let source = self.world.source(self.id(&params.text_document.uri)?);
+        let mut tokens = Tokens { source, prev: Position::new(0, 0), data: vec![] };
+        tokens.visit(&LinkedNode::new(source.root()), None);
+        Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data: tokens.data,
+        }))
+    }
+
+    /// Compile a file and render its pages to SVG.
+    fn preview(&mut self, params: PreviewParams) -> Option<PreviewResult> {
+        self.id(&params.text_document.uri)?;
+        self.last = Some(params.text_document.uri);
+        self.compile();
+        let pages = self
+            .document
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| typst::export::svg_with_prefix(frame, &format!("p{i}-")))
+            .collect();
+        Some(PreviewResult { pages })
+    }
+
+    /// Drop all cached files and reapply the contents of the open editor
+    /// buffers on top of the files on disk.
+    fn reset(&mut self) {
+        let buffers: Vec<_> = self
+            .documents
+            .drain()
+            .map(|(uri, id)| {
+                let source = self.world.source(id);
+                (uri, source.path().to_owned(), source.text().to_owned())
+            })
+            .collect();
+
+        // The spans in the old document would point into the dropped files.
+        self.world.reset();
+        self.document = Document::default();
+        for (uri, path, text) in buffers {
+            if let Ok(id) = self.world.update(&path, text) {
+                self.documents.insert(uri, id);
+            }
+        }
+    }
+
+    /// Compile the main file and publish the resulting diagnostics.
+    fn compile(&mut self) {
+        let main = match &self.main {
+            Some(path) => self.world.resolve(path).ok(),
+            None => self.last.as_ref().and_then(|uri| self.id(uri)),
+        };
+
+        let Some(main) = main else { return };
+        self.world.main = main;
+
+        let diagnostics = match typst::compile(&self.world) {
+            Ok((document, warnings)) => {
+                self.document = document;
+                warnings
+            }
+            Err(errors) => *errors,
+        };
+
+        self.publish(diagnostics);
+    }
+
+    /// Send diagnostics to the client, grouped by file.
+    fn publish(&mut self, errors: Vec<SourceError>) {
+        let mut grouped: HashMap<Url, Vec<lsp_types::Diagnostic>> = HashMap::new();
+        for error in errors {
+            // Errors without a location are reported at the start of the main
+            // file.
+            let (source, range) = if error.span.is_detached() {
+                (self.world.main(), 0..0)
+            } else {
+                let source = self.world.source(error.span.source());
+                (source, source.range(error.span))
+            };
+
+            let Ok(uri) = Url::from_file_path(source.path()) else { continue };
+            grouped.entry(uri).or_default().push(lsp_types::Diagnostic {
+                range: to_range(source, range),
+                severity: Some(match error.severity {
+                    Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+                    Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+                }),
+                source: Some("typst".into()),
+                message: error.message.into(),
+                ..Default::default()
+            });
+        }
+
+        // Clear the diagnostics of files that no longer have any.
+        for uri in std::mem::take(&mut self.published) {
+            grouped.entry(uri).or_default();
+        }
+
+        for (uri, diagnostics) in grouped {
+            if !diagnostics.is_empty() {
+                self.published.insert(uri.clone());
+            }
+
+            let params = PublishDiagnosticsParams { uri, diagnostics, version: None };
+            let notification =
+                Notification::new(PublishDiagnostics::METHOD.into(), params);
+            self.send(notification.into());
+        }
+    }
+
+    /// The id of an open file.
+    fn id(&self, uri: &Url) -> Option<SourceId> {
+        self.documents.get(uri).copied()
+    }
+
+    /// Convert a byte range in a file into a location for the client.
+    fn location(&self, id: SourceId, range: std::ops::Range<usize>) -> Option<Location> {
+        let source = self.world.source(id);
+        let uri = Url::from_file_path(source.path()).ok()?;
+        Some(Location::new(uri, to_range(source, range)))
+    }
+
+    /// Send a message to the client.
+    fn send(&self, message: Message) {
+        // The client may already be gone, in which case the receive loop ends
+        // on its own.
+        let _ = self.connection.sender.send(message);
+    }
+}
+
+/// Convert an outline item into a symbol for the client.
+fn document_symbol(source: &Source, symbol: Symbol) -> DocumentSymbol {
+    let kind = match symbol.kind {
+        SymbolKind::Heading(_) => lsp_types::SymbolKind::NAMESPACE,
+        SymbolKind::Function => lsp_types::SymbolKind::FUNCTION,
+        SymbolKind::Variable => lsp_types::SymbolKind::VARIABLE,
+        SymbolKind::Label => lsp_types::SymbolKind::KEY,
+        SymbolKind::SetRule | SymbolKind::ShowRule => lsp_types::SymbolKind::EVENT,
+    };
+
+    let children = symbol
+        .children
+        .into_iter()
+        .map(|child| document_symbol(source, child))
+        .collect();
+
+    #[allow(deprecated)]
+    DocumentSymbol {
+        name: symbol.name.into(),
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: to_range(source, symbol.range),
+        selection_range: to_range(source, symbol.selection),
+        children: Some(children),
+    }
+}
+
+/// Wrap Markdown text into documentation for the client.
+fn markdown(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent { kind: MarkupKind::Markdown, value })
+}
+
+/// Answer a request by deserializing its parameters and calling `f`.
+fn respond<P, R>(req: Request, f: impl FnOnce(P) -> R) -> Response
+where
+    P: DeserializeOwned,
+    R: Serialize,
+{
+    match serde_json::from_value(req.params) {
+        Ok(params) => Response::new_ok(req.id, f(params)),
+        Err(err) => {
+            Response::new_err(req.id, ErrorCode::InvalidParams as i32, err.to_string())
+        }
+    }
+}
+
+/// Answer a request like [`respond`], but report a failure of `f` to the
+/// client.
+fn try_respond<P, R>(req: Request, f: impl FnOnce(P) -> StrResult<R>) -> Response
+where
+    P: DeserializeOwned,
+    R: Serialize,
+{
+    match serde_json::from_value(req.params) {
+        Ok(params) => match f(params) {
+            Ok(result) => Response::new_ok(req.id, result),
+            Err(message) => {
+                Response::new_err(req.id, ErrorCode::RequestFailed as i32, message.into())
+            }
+        },
+        Err(err) => {
+            Response::new_err(req.id, ErrorCode::InvalidParams as i32, err.to_string())
+        }
+    }
+}
+
+/// Handle a notification by deserializing its parameters and calling `f`.
+fn notify<P>(notification: Notification, f: impl FnOnce(P))
+where
+    P: DeserializeOwned,
+{
+    if let Ok(params) = serde_json::from_value(notification.params) {
+        f(params);
+    }
+}
+
+/// Collects semantic tokens in the relative encoding of the LSP.
+struct Tokens<'a> {
+    source: &'a Source,
+    prev: Position,
+    data: Vec<SemanticToken>,
+}
+
+impl Tokens<'_> {
+    /// Emit tokens for a node's leaves. Leaves without a tag of their own
+    /// inherit the tag of their closest highlighted ancestor.
+    fn visit(&mut self, node: &LinkedNode, inherited: Option<Tag>) {
+        let tag = highlight(node).or(inherited);
+        if node.children().len() > 0 {
+            for child in node.children() {
+                self.visit(&child, tag);
+            }
+        } else if let Some(tag) = tag {
+            self.leaf(node, tag);
+        }
+    }
+
+    /// Emit a token for a leaf, split into one token per line because tokens
+    /// must not span multiple lines.
+    fn leaf(&mut self, node: &LinkedNode, tag: Tag) {
+        let mut start = node.offset();
+        let mut len = 0;
+        for c in node.text().chars() {
+            if is_newline(c) {
+                self.push(start, len, tag);
+                start += len + c.len_utf8();
+                len = 0;
+            } else {
+                len += c.len_utf8();
+            }
+        }
+        self.push(start, len, tag);
+    }
+
+    /// Emit a single-line token.
+    fn push(&mut self, start: usize, len: usize, tag: Tag) {
+        if len == 0 {
+            return;
+        }
+
+        let position = to_position(self.source, start);
+        let length = self.source.byte_to_utf16(start + len).unwrap_or_default()
+            - self.source.byte_to_utf16(start).unwrap_or_default();
+
+        let delta_line = position.line - self.prev.line;
+        let delta_start = if delta_line == 0 {
+            position.character - self.prev.character
+        } else {
+            position.character
+        };
+
+        self.data.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: length as u32,
+            token_type: Tag::LIST.iter().position(|&t| t == tag).unwrap_or_default()
+                as u32,
+            token_modifiers_bitset: 0,
+        });
+
+        self.prev = position;
+    }
+}
+
+/// The semantic token type for a highlighting tag.
+fn token_type(tag: Tag) -> SemanticTokenType {
+    match tag {
+        Tag::Comment => SemanticTokenType::COMMENT,
+        Tag::Punctuation => SemanticTokenType::new("punctuation"),
+        Tag::Escape => SemanticTokenType::new("escape"),
+        Tag::Strong => SemanticTokenType::new("strong"),
+        Tag::Emph => SemanticTokenType::new("emph"),
+        Tag::Link => SemanticTokenType::new("link"),
+        Tag::Raw => SemanticTokenType::new("raw"),
+        Tag::Label => SemanticTokenType::new("label"),
+        Tag::Ref => SemanticTokenType::new("ref"),
+        Tag::Heading => SemanticTokenType::new("heading"),
+        Tag::ListMarker => SemanticTokenType::new("marker"),
+        Tag::ListTerm => SemanticTokenType::new("term"),
+        Tag::MathDelimiter => SemanticTokenType::new("delim"),
+        Tag::MathOperator => SemanticTokenType::new("mathOperator"),
+        Tag::Keyword => SemanticTokenType::KEYWORD,
+        Tag::Operator => SemanticTokenType::OPERATOR,
+        Tag::Number => SemanticTokenType::NUMBER,
+        Tag::String => SemanticTokenType::STRING,
+        Tag::Function => SemanticTokenType::FUNCTION,
+        Tag::Interpolated => SemanticTokenType::VARIABLE,
+        Tag::Error => SemanticTokenType::new("error"),
+    }
+}
+
+/// Convert a Typst completion into an LSP completion item that replaces the
+/// given range.
+fn completion_item(
+    completion: TypstCompletion,
+    range: lsp_types::Range,
+) -> CompletionItem {
+    let kind = match completion.kind {
+        CompletionKind::Syntax => CompletionItemKind::SNIPPET,
+        CompletionKind::Func => CompletionItemKind::FUNCTION,
+        CompletionKind::Param => CompletionItemKind::VARIABLE,
+        CompletionKind::Constant => CompletionItemKind::CONSTANT,
+        CompletionKind::Symbol(_) => CompletionItemKind::TEXT,
+    };
+
+    let apply = completion.apply.as_deref().unwrap_or(&completion.label);
+    CompletionItem {
+        label: completion.label.to_string(),
+        kind: Some(kind),
+        detail: completion.detail.map(Into::into),
+        text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+            range,
+            to_snippet(apply),
+        ))),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        ..Default::default()
+    }
+}
+
+/// Convert Typst's snippet syntax (`${lhs} + ${rhs}`) into the LSP's numbered
+/// snippet syntax (`${1:lhs} + ${2:rhs}`).
+fn to_snippet(apply: &str) -> String {
+    let mut snippet = String::new();
+    let mut rest = apply;
+    let mut index = 1;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else { break };
+        escape_snippet(&mut snippet, &rest[..start]);
+        let name = &rest[start + 2..start + len];
+        write!(snippet, "${{{index}:{name}}}").unwrap();
+        rest = &rest[start + len + 1..];
+        index += 1;
+    }
+    escape_snippet(&mut snippet, rest);
+    snippet
+}
+
+/// Escape characters with a special meaning in LSP snippets.
+fn escape_snippet(buf: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(c, '$' | '\\' | '}') {
+            buf.push('\\');
+        }
+        buf.push(c);
+    }
+}
+
+/// Convert an LSP position (line and UTF-16 column) into a byte offset.
+fn to_offset(source: &Source, position: Position) -> Option<usize> {
+    let line = source.line_to_byte(position.line as usize)?;
+    let utf16 = source.byte_to_utf16(line)? + position.character as usize;
+    source.utf16_to_byte(utf16.min(source.len_utf16()))
+}
+
+/// Convert a byte offset into an LSP position (line and UTF-16 column).
+fn to_position(source: &Source, offset: usize) -> Position {
+    let line = source.byte_to_line(offset).unwrap_or_default();
+    let start = source.line_to_byte(line).unwrap_or_default();
+    let column = source.byte_to_utf16(offset).unwrap_or_default()
+        - source.byte_to_utf16(start).unwrap_or_default();
+    Position::new(line as u32, column as u32)
+}
+
+/// Convert a byte range into an LSP range.
+fn to_range(source: &Source, range: std::ops::Range<usize>) -> lsp_types::Range {
+    lsp_types::Range::new(
+        to_position(source, range.start),
+        to_position(source, range.end),
+    )
+}
@@ -1,82 +1,311 @@
 use std::cell::{RefCell, RefMut};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
 use std::fs::{self, File};
 use std::hash::Hash;
 use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::process;
+use std::str::FromStr;
 
+use chrono::Datelike;
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::term::{self, termcolor};
-use comemo::Prehashed;
+use comemo::{Prehashed, Track};
 use elsa::FrozenVec;
 use memmap2::Mmap;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use once_cell::unsync::OnceCell;
 use pico_args::Arguments;
 use same_file::{is_same_file, Handle};
+use serde::{Deserialize, Serialize};
 use siphasher::sip128::{Hasher128, SipHasher};
 use termcolor::{ColorChoice, StandardStream, WriteColor};
-use typst::diag::{FileError, FileResult, SourceError, StrResult};
-use typst::eval::Library;
+use typst::diag::{At, FileError, FileResult, Severity, SourceError, StrResult};
+use typst::doc::Document;
+use typst::eval::{Datetime, Dict, Library, PackageSpec, Value};
 use typst::font::{Font, FontBook, FontInfo, FontVariant};
-use typst::syntax::{Source, SourceId};
-use typst::util::{Buffer, PathExt};
+use typst::geom::{Color, Point};
+use typst::ide::Jump;
+use typst::model::{Content, Introspector, Selector};
+use typst::syntax::{Source, SourceId, Span};
+use typst::util::{timing, Buffer, PathExt};
 use typst::World;
 use walkdir::WalkDir;
 
+mod lsp;
+mod serve;
+
 type CodespanResult<T> = Result<T, CodespanError>;
 type CodespanError = codespan_reporting::files::Error;
 
 /// What to do.
 enum Command {
     Compile(CompileCommand),
+    Query(QueryCommand),
     Fonts(FontsCommand),
+    Lsp(LspCommand),
+    Fmt(FmtCommand),
 }
 
-/// Compile a .typ file into a PDF file.
+/// Compile a .typ file into a PDF, PNG or SVG file.
 struct CompileCommand {
     input: PathBuf,
     output: PathBuf,
+    format: OutputFormat,
     root: Option<PathBuf>,
+    inputs: Dict,
+    font_paths: Vec<PathBuf>,
     watch: bool,
+    serve: Option<SocketAddr>,
+    timings: Option<PathBuf>,
+    deps: Option<PathBuf>,
+    ppi: f32,
+    diagnostic_format: DiagnosticFormat,
+    max_iterations: usize,
+}
+
+/// The format to export a compiled document into.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum OutputFormat {
+    Pdf,
+    Png,
+    Svg,
+}
+
+impl OutputFormat {
+    /// Determine the output format from a path's file extension.
+    fn from_path(path: &Path) -> StrResult<Self> {
+        let ext = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        Ok(match ext.to_lowercase().as_str() {
+            "pdf" => Self::Pdf,
+            "png" => Self::Png,
+            "svg" => Self::Svg,
+            _ => Err("unknown output format (supported are pdf, png and svg)")?,
+        })
+    }
 }
 
 const HELP: &'static str = "\
-typst creates PDF files from .typ files
+typst creates PDF, PNG and SVG files from .typ files
 
 USAGE:
-  typst [OPTIONS] <input.typ> [output.pdf]
-  typst [SUBCOMMAND] ...
+  typst [OPTIONS] <SUBCOMMAND>
+
+OPTIONS:
+  -h, --help     Print this help
+  -V, --version  Print the CLI's version
+
+SUBCOMMANDS:
+  c, compile     Compile a .typ file into a PDF, PNG or SVG file
+  w, watch       Watch the inputs and recompile on changes
+  q, query       Query elements of a compiled document and print them as JSON
+  fonts          List all discovered system fonts
+  lsp            Run a language server over stdio
+  fmt            Format .typ files
+";
+
+const HELP_COMPILE: &'static str = "\
+typst compile compiles a .typ file into a PDF, PNG or SVG file
+
+USAGE:
+  typst compile [OPTIONS] <input.typ> [output.pdf]
 
 ARGS:
   <input.typ>    Path to input Typst file
-  [output.pdf]   Path to output PDF file
+  [output.pdf]   Path to output PDF, PNG or SVG file. PNG and SVG files with
+                 more than one page must contain `{n}` in the file name, which
+                 is replaced with the page number.
 
 OPTIONS:
   -h, --help     Print this help
-  -V, --version  Print the CLI's version
-  -w, --watch    Watch the inputs and recompile on changes
   --root <dir>   Configure the root for absolute paths
+  --input <key=value>
+                 Add a string key-value pair that is visible to the document
+                 through `sys.inputs`. Can be passed multiple times.
+  --font-path <dir>
+                 Add a directory to search for fonts in. Can be passed
+                 multiple times. Further directories can be listed in the
+                 TYPST_FONT_PATHS environment variable.
+  --diagnostic-format <format>
+                 The format in which to print diagnostics: human, short or
+                 json [default: human]
+  --max-iterations <n>
+                 The maximum number of layout iterations until introspections
+                 must have stabilized [default: 5]
+  --ppi <ppi>    The resolution of exported PNG files in pixels per inch
+                 [default: 144]
+  --timings <trace.json>
+                 Record how long parsing, evaluation, show rules, layout and
+                 export take and write the timings in Chrome's trace event
+                 format
+  --deps <deps.d>
+                 Write the files the compilation read as a Makefile rule, or
+                 as JSON if the path ends in .json
+";
 
-SUBCOMMANDS:
-  --fonts        List all discovered system fonts
+const HELP_WATCH: &'static str = "\
+typst watch compiles a .typ file and recompiles it on changes
+
+USAGE:
+  typst watch [OPTIONS] <input.typ> [output.pdf]
+
+ARGS:
+  <input.typ>    Path to input Typst file
+  [output.pdf]   Path to output PDF, PNG or SVG file. PNG and SVG files with
+                 more than one page must contain `{n}` in the file name, which
+                 is replaced with the page number.
+
+OPTIONS:
+  -h, --help     Print this help
+  --root <dir>   Configure the root for absolute paths
+  --input <key=value>
+                 Add a string key-value pair that is visible to the document
+                 through `sys.inputs`. Can be passed multiple times.
+  --font-path <dir>
+                 Add a directory to search for fonts in. Can be passed
+                 multiple times. Further directories can be listed in the
+                 TYPST_FONT_PATHS environment variable.
+  --diagnostic-format <format>
+                 The format in which to print diagnostics: human, short or
+                 json [default: human]
+  --max-iterations <n>
+                 The maximum number of layout iterations until introspections
+                 must have stabilized [default: 5]
+  --ppi <ppi>    The resolution of exported PNG files in pixels per inch
+                 [default: 144]
+  --timings <trace.json>
+                 Record how long parsing, evaluation, show rules, layout and
+                 export take and write the timings in Chrome's trace event
+                 format
+  --deps <deps.d>
+                 Write the files the compilation read as a Makefile rule, or
+                 as JSON if the path ends in .json
+  --serve <addr> Serve a live preview of the document at the given address,
+                 e.g. 127.0.0.1:3000. Clicking on a page prints the source
+                 location that produced the clicked content.
+";
+
+/// Query a compiled document for elements.
+struct QueryCommand {
+    input: PathBuf,
+    selector: String,
+    field: Option<String>,
+    root: Option<PathBuf>,
+    inputs: Dict,
+    font_paths: Vec<PathBuf>,
+    diagnostic_format: DiagnosticFormat,
+    max_iterations: usize,
+}
+
+const HELP_QUERY: &'static str = "\
+typst query compiles a .typ file and prints the elements matching a selector
+as JSON
+
+USAGE:
+  typst query [OPTIONS] <input.typ> <selector>
+
+ARGS:
+  <input.typ>      Path to input Typst file
+  <selector>       Typst code evaluating to a selector, e.g. `<intro>`,
+                   `heading` or `figure.where(kind: image)`
+
+OPTIONS:
+  -h, --help       Print this help
+  --root <dir>     Configure the root for absolute paths
+  --input <key=value>
+                   Add a string key-value pair that is visible to the document
+                   through `sys.inputs`. Can be passed multiple times.
+  --font-path <dir>
+                   Add a directory to search for fonts in. Can be passed
+                   multiple times. Further directories can be listed in the
+                   TYPST_FONT_PATHS environment variable.
+  --diagnostic-format <format>
+                   The format in which to print diagnostics: human, short or
+                   json [default: human]
+  --max-iterations <n>
+                   The maximum number of layout iterations until
+                   introspections must have stabilized [default: 5]
+  --field <name>   Print only the given field of each matching element,
+                   or null for elements without it
+";
+
+/// Run a language server that talks to an editor over stdio.
+struct LspCommand {
+    root: Option<PathBuf>,
+    main: Option<PathBuf>,
+    inputs: Dict,
+    font_paths: Vec<PathBuf>,
+    max_iterations: usize,
+}
+
+const HELP_LSP: &'static str = "\
+typst lsp runs a language server that communicates over stdin and stdout
+
+USAGE:
+  typst lsp [OPTIONS]
+
+OPTIONS:
+  -h, --help     Print this help
+  --root <dir>   Configure the root for absolute paths [default: the
+                 workspace root reported by the editor]
+  --main <file>  The file to compile for diagnostics and previews [default:
+                 the most recently edited file]
+  --input <key=value>
+                 Add a string key-value pair that is visible to the document
+                 through `sys.inputs`. Can be passed multiple times.
+  --font-path <dir>
+                 Add a directory to search for fonts in. Can be passed
+                 multiple times. Further directories can be listed in the
+                 TYPST_FONT_PATHS environment variable.
+  --max-iterations <n>
+                 The maximum number of layout iterations until introspections
+                 must have stabilized [default: 5]
 ";
 
 /// List discovered system fonts.
 struct FontsCommand {
+    font_paths: Vec<PathBuf>,
     variants: bool,
 }
 
 const HELP_FONTS: &'static str = "\
-typst --fonts lists all discovered system fonts
+typst fonts lists all discovered system fonts
 
 USAGE:
-  typst --fonts [OPTIONS]
+  typst fonts [OPTIONS]
 
 OPTIONS:
   -h, --help     Print this help
   --variants     Also list style variants of each font family
+  --font-path <dir>
+                 Add a directory to search for fonts in. Can be passed
+                 multiple times. Further directories can be listed in the
+                 TYPST_FONT_PATHS environment variable.
+";
+
+/// Format .typ files.
+struct FmtCommand {
+    paths: Vec<PathBuf>,
+    check: bool,
+}
+
+const HELP_FMT: &'static str = "\
+typst fmt formats .typ files in place
+
+USAGE:
+  typst fmt [OPTIONS] [paths...]
+
+ARGS:
+  [paths...]     Files and directories to format. Directories are searched
+                 recursively for .typ files. [default: the current directory]
+
+OPTIONS:
+  -h, --help     Print this help
+  --check        Don't write any files, but fail if some of them are not
+                 formatted
 ";
 
 /// Entry point.
@@ -100,22 +329,103 @@ fn parse_args() -> StrResult<Command> {
     }
 
     let help = args.contains(["-h", "--help"]);
+    let subcommand = args.subcommand().map_err(|_| "invalid subcommand")?;
 
-    let command = if args.contains("--fonts") {
-        if help {
-            print_help(HELP_FONTS);
+    let command = match subcommand.as_deref() {
+        Some("compile" | "c") => {
+            if help {
+                print_help(HELP_COMPILE);
+            }
+
+            Command::Compile(parse_compile(&mut args, false)?)
         }
+        Some("watch" | "w") => {
+            if help {
+                print_help(HELP_WATCH);
+            }
 
-        Command::Fonts(FontsCommand { variants: args.contains("--variants") })
-    } else {
-        if help {
-            print_help(HELP);
+            Command::Compile(parse_compile(&mut args, true)?)
+        }
+        Some("query" | "q") => {
+            if help {
+                print_help(HELP_QUERY);
+            }
+
+            let root =
+                args.opt_value_from_str("--root").map_err(|_| "missing root path")?;
+            let inputs = parse_inputs(&mut args)?;
+            let font_paths = parse_font_paths(&mut args)?;
+            let diagnostic_format = parse_diagnostic_format(&mut args)?;
+            let max_iterations = parse_max_iterations(&mut args)?;
+            let field =
+                args.opt_value_from_str("--field").map_err(|_| "missing field")?;
+            let input = args.free_from_str().map_err(|_| "missing input file")?;
+            let selector = args.free_from_str().map_err(|_| "missing selector")?;
+            Command::Query(QueryCommand {
+                input,
+                selector,
+                field,
+                root,
+                inputs,
+                font_paths,
+                diagnostic_format,
+                max_iterations,
+            })
+        }
+        Some("fonts") => {
+            if help {
+                print_help(HELP_FONTS);
+            }
+
+            let font_paths = parse_font_paths(&mut args)?;
+            let variants = args.contains("--variants");
+            Command::Fonts(FontsCommand { font_paths, variants })
         }
+        Some("lsp") => {
+            if help {
+                print_help(HELP_LSP);
+            }
 
-        let root = args.opt_value_from_str("--root").map_err(|_| "missing root path")?;
-        let watch = args.contains(["-w", "--watch"]);
-        let (input, output) = parse_input_output(&mut args, "pdf")?;
-        Command::Compile(CompileCommand { input, output, watch, root })
+            let root =
+                args.opt_value_from_str("--root").map_err(|_| "missing root path")?;
+            let main =
+                args.opt_value_from_str("--main").map_err(|_| "missing main file")?;
+            let inputs = parse_inputs(&mut args)?;
+            let font_paths = parse_font_paths(&mut args)?;
+            let max_iterations = parse_max_iterations(&mut args)?;
+            Command::Lsp(LspCommand { root, main, inputs, font_paths, max_iterations })
+        }
+        Some("fmt") => {
+            if help {
+                print_help(HELP_FMT);
+            }
+
+            let check = args.contains("--check");
+            let mut paths = vec![];
+            while let Some(path) = args.opt_free_from_str().map_err(|_| "invalid path")? {
+                paths.push(path);
+            }
+
+            if paths.is_empty() {
+                paths.push(PathBuf::from("."));
+            }
+
+            Command::Fmt(FmtCommand { paths, check })
+        }
+        Some(other) => {
+            if help {
+                print_help(HELP);
+            }
+
+            Err(format!("unknown subcommand `{other}`"))?
+        }
+        None => {
+            if help {
+                print_help(HELP);
+            }
+
+            Err("missing subcommand")?
+        }
     };
 
     // Don't allow excess arguments.
@@ -127,6 +437,110 @@ fn parse_args() -> StrResult<Command> {
     Ok(command)
 }
 
+/// Parse the arguments of the `compile` and `watch` subcommands.
+fn parse_compile(args: &mut Arguments, watch: bool) -> StrResult<CompileCommand> {
+    let root = args.opt_value_from_str("--root").map_err(|_| "missing root path")?;
+    let inputs = parse_inputs(args)?;
+    let font_paths = parse_font_paths(args)?;
+    let diagnostic_format = parse_diagnostic_format(args)?;
+    let max_iterations = parse_max_iterations(args)?;
+    let ppi = args
+        .opt_value_from_str("--ppi")
+        .map_err(|_| "invalid pixels per inch")?
+        .unwrap_or(144.0);
+
+    if !(ppi > 0.0 && f32::is_finite(ppi)) {
+        Err("pixels per inch must be a positive number")?;
+    }
+
+    let timings = args
+        .opt_value_from_str("--timings")
+        .map_err(|_| "missing timings path")?;
+    let deps = args
+        .opt_value_from_str("--deps")
+        .map_err(|_| "missing dependency file path")?;
+    let serve = if watch {
+        args.opt_value_from_str("--serve")
+            .map_err(|_| "invalid address to serve at")?
+    } else {
+        None
+    };
+
+    let (input, output) = parse_input_output(args, "pdf")?;
+    let format = OutputFormat::from_path(&output)?;
+    Ok(CompileCommand {
+        input,
+        output,
+        format,
+        root,
+        inputs,
+        font_paths,
+        watch,
+        serve,
+        timings,
+        deps,
+        ppi,
+        diagnostic_format,
+        max_iterations,
+    })
+}
+
+/// Parse the format in which to print diagnostics.
+fn parse_diagnostic_format(args: &mut Arguments) -> StrResult<DiagnosticFormat> {
+    Ok(args
+        .opt_value_from_str("--diagnostic-format")
+        .map_err(|_| "invalid diagnostic format (expected human, short or json)")?
+        .unwrap_or(DiagnosticFormat::Human))
+}
+
+/// Parse the maximum number of layout iterations.
+fn parse_max_iterations(args: &mut Arguments) -> StrResult<usize> {
+    let max = args
+        .opt_value_from_str("--max-iterations")
+        .map_err(|_| "invalid maximum number of iterations")?
+        .unwrap_or(5);
+
+    if max == 0 {
+        Err("maximum number of iterations must be at least one")?;
+    }
+
+    Ok(max)
+}
+
+/// Parse the `key=value` pairs passed with `--input` into a dictionary.
+fn parse_inputs(args: &mut Arguments) -> StrResult<Dict> {
+    let pairs: Vec<String> =
+        args.values_from_str("--input").map_err(|_| "missing input")?;
+
+    let mut inputs = Dict::new();
+    for pair in pairs {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("input `{pair}` must have the form key=value"))?;
+        if key.is_empty() {
+            Err(format!("input `{pair}` must have a non-empty key"))?;
+        }
+        inputs.insert(key.into(), Value::Str(value.into()));
+    }
+
+    Ok(inputs)
+}
+
+/// Parse the directories passed with `--font-path` and those listed in the
+/// `TYPST_FONT_PATHS` environment variable.
+fn parse_font_paths(args: &mut Arguments) -> StrResult<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> =
+        args.values_from_str("--font-path").map_err(|_| "missing font path")?;
+
+    if let Some(var) = std::env::var_os("TYPST_FONT_PATHS") {
+        paths.extend(
+            std::env::split_paths(&var).filter(|path| !path.as_os_str().is_empty()),
+        );
+    }
+
+    Ok(paths)
+}
+
 /// Parse two freestanding path arguments, with the output path being optional.
 /// If it is omitted, it is determined from the input path's file stem plus the
 /// given extension.
@@ -176,25 +590,23 @@ fn print_error(msg: &str) -> io::Result<()> {
 fn dispatch(command: Command) -> StrResult<()> {
     match command {
         Command::Compile(command) => compile(command),
+        Command::Query(command) => query(command),
         Command::Fonts(command) => fonts(command),
+        Command::Lsp(command) => lsp::run(command),
+        Command::Fmt(command) => fmt(command),
     }
 }
 
 /// Execute a compilation command.
 fn compile(command: CompileCommand) -> StrResult<()> {
-    let root = if let Some(root) = &command.root {
-        root.clone()
-    } else if let Some(dir) = command.input.parent() {
-        dir.into()
-    } else {
-        PathBuf::new()
-    };
-
     // Create the world that serves sources, fonts and files.
-    let mut world = SystemWorld::new(root);
+    let root = determine_root(&command.input, command.root.as_deref());
+    let searcher = FontSearcher::system(&command.font_paths);
+    let mut world = SystemWorld::new(root, command.inputs.clone(), searcher);
+    world.max_iterations = command.max_iterations;
 
     // Perform initial compilation.
-    compile_once(&mut world, &command)?;
+    let mut document = compile_once(&mut world, &command)?;
 
     if !command.watch {
         return Ok(());
@@ -202,67 +614,328 @@ fn compile(command: CompileCommand) -> StrResult<()> {
 
     // Setup file watching.
     let (tx, rx) = std::sync::mpsc::channel();
-    let mut watcher = RecommendedWatcher::new(tx, notify::Config::default())
-        .map_err(|_| "failed to watch directory")?;
+    let events = tx.clone();
+    let mut watcher = RecommendedWatcher::new(
+        move |event| {
+            events.send(Message::Event(event)).ok();
+        },
+        notify::Config::default(),
+    )
+    .map_err(|_| "failed to watch directory")?;
 
     // Watch this directory recursively.
     watcher
         .watch(Path::new("."), RecursiveMode::Recursive)
         .map_err(|_| "failed to watch directory")?;
 
-    // Handle events.
+    // Serve the live preview.
+    let server = command.serve.map(|addr| serve::Server::start(addr, tx)).transpose()?;
+    if let (Some(server), Some(document)) = (&server, &document) {
+        server.update(document);
+    }
+
+    // Handle events and clicks.
     let timeout = std::time::Duration::from_millis(100);
     loop {
         let mut recompile = false;
-        for event in rx
+        for message in rx
             .recv()
             .into_iter()
             .chain(std::iter::from_fn(|| rx.recv_timeout(timeout).ok()))
         {
-            let event = event.map_err(|_| "failed to watch directory")?;
-            if event
-                .paths
-                .iter()
-                .all(|path| is_same_file(path, &command.output).unwrap_or(false))
-            {
-                continue;
+            match message {
+                Message::Event(event) => {
+                    let event = event.map_err(|_| "failed to watch directory")?;
+                    if event.paths.iter().all(|path| command.is_output(path)) {
+                        continue;
+                    }
+
+                    recompile |= world.relevant(&event);
+                }
+                Message::Click(page, click) => {
+                    if let (Some(server), Some(document)) = (&server, &document) {
+                        jump(&world, server, document, page, click);
+                    }
+                }
             }
-
-            recompile |= world.relevant(&event);
         }
 
         if recompile {
-            compile_once(&mut world, &command)?;
+            // Keep showing the last successful compilation on errors.
+            if let Some(compiled) = compile_once(&mut world, &command)? {
+                if let Some(server) = &server {
+                    server.update(&compiled);
+                }
+                document = Some(compiled);
+            }
+        }
+    }
+}
+
+/// A message to the watch loop.
+enum Message {
+    /// A change in the file system.
+    Event(notify::Result<notify::Event>),
+    /// A click at a point on a page of the live preview.
+    Click(usize, Point),
+}
+
+/// Report where a click in the live preview leads to.
+fn jump(
+    world: &SystemWorld,
+    server: &serve::Server,
+    document: &Document,
+    page: usize,
+    click: Point,
+) {
+    let Some(frame) = document.pages.get(page) else { return };
+    match typst::ide::jump_from_click(world, &document.pages, frame, click) {
+        Some(Jump::Source(id, offset)) => {
+            println!("clicked {}", DiagnosticLocation::new(world, id, offset..offset));
+        }
+        Some(Jump::Url(url)) => println!("clicked link to {url}"),
+        Some(Jump::Position(position)) => {
+            server.scroll(position.page.get() - 1, position.point.y);
         }
+        None => {}
+    }
+}
+
+/// Determine the root for absolute paths, which defaults to the directory of
+/// the input file.
+fn determine_root(input: &Path, root: Option<&Path>) -> PathBuf {
+    if let Some(root) = root {
+        root.into()
+    } else if let Some(dir) = input.parent() {
+        dir.into()
+    } else {
+        PathBuf::new()
     }
 }
 
-/// Compile a single time.
-fn compile_once(world: &mut SystemWorld, command: &CompileCommand) -> StrResult<()> {
+/// Compile a single time, returning the document if compilation succeeded.
+fn compile_once(
+    world: &mut SystemWorld,
+    command: &CompileCommand,
+) -> StrResult<Option<Document>> {
     status(command, Status::Compiling).unwrap();
 
+    if command.timings.is_some() {
+        timing::enable();
+    }
+
     world.reset();
     world.main = world.resolve(&command.input).map_err(|err| err.to_string())?;
 
-    match typst::compile(world) {
-        // Export the PDF.
-        Ok(document) => {
-            let buffer = typst::export::pdf(&document);
-            fs::write(&command.output, buffer).map_err(|_| "failed to write PDF file")?;
+    let document = match typst::compile(world) {
+        // Export the document and print warnings, if any.
+        Ok((document, warnings)) => {
+            export(&document, command)?;
+            if let Some(path) = &command.deps {
+                write_deps(world, command, &document, path)?;
+            }
             status(command, Status::Success).unwrap();
+            print_diagnostics(&world, warnings, command.diagnostic_format)
+                .map_err(|_| "failed to print diagnostics")?;
+            Some(document)
         }
 
         // Print diagnostics.
         Err(errors) => {
             status(command, Status::Error).unwrap();
-            print_diagnostics(&world, *errors)
+            print_diagnostics(&world, *errors, command.diagnostic_format)
                 .map_err(|_| "failed to print diagnostics")?;
+            None
+        }
+    };
+
+    if let Some(path) = &command.timings {
+        write_timings(path)?;
+    }
+
+    Ok(document)
+}
+
+/// Write the timings recorded during compilation in Chrome's trace event
+/// format, which can be viewed in `chrome://tracing` or Perfetto.
+fn write_timings(path: &Path) -> StrResult<()> {
+    let Some(timings) = timing::take() else { return Ok(()) };
+
+    let events: Vec<_> = timings
+        .events
+        .iter()
+        .map(|event| {
+            serde_json::json!({
+                "name": event.name.as_str(),
+                "cat": event.cat,
+                "ph": "X",
+                "ts": event.start.as_secs_f64() * 1e6,
+                "dur": event.duration.as_secs_f64() * 1e6,
+                "pid": 1,
+                "tid": 1,
+            })
+        })
+        .collect();
+
+    let memo: serde_json::Map<_, _> = timings
+        .memo
+        .iter()
+        .map(|(func, stats)| {
+            let stats = serde_json::json!({
+                "calls": stats.calls,
+                "hits": stats.hits(),
+                "misses": stats.misses,
+            });
+            (func.to_string(), stats)
+        })
+        .collect();
+
+    let trace = serde_json::json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+        "otherData": { "memoization": memo },
+    });
+
+    let file = File::create(path).map_err(|_| "failed to create timings file")?;
+    serde_json::to_writer(io::BufWriter::new(file), &trace)
+        .map_err(|_| "failed to write timings file")?;
+
+    Ok(())
+}
+
+/// Export into the target format.
+fn export(document: &Document, command: &CompileCommand) -> StrResult<()> {
+    match command.format {
+        OutputFormat::Pdf => {
+            let buffer = typst::export::pdf(document);
+            fs::write(&command.output, buffer).map_err(|_| "failed to write PDF file")?;
+        }
+        OutputFormat::Png => {
+            let pixel_per_pt = command.ppi / 72.0;
+            for (frame, path) in document.pages.iter().zip(page_paths(document, command)?)
+            {
+                let pixmap = typst::export::render(frame, pixel_per_pt, Color::WHITE);
+                pixmap.save_png(path).map_err(|_| "failed to write PNG file")?;
+            }
+        }
+        OutputFormat::Svg => {
+            for (frame, path) in document.pages.iter().zip(page_paths(document, command)?)
+            {
+                let svg = typst::export::svg(frame);
+                fs::write(path, svg).map_err(|_| "failed to write SVG file")?;
+            }
         }
     }
 
     Ok(())
 }
 
+/// Write the files that the compilation read, like sources, images, data files
+/// and fonts, as a Makefile rule or, if the path ends in `.json`, as a JSON
+/// object.
+fn write_deps(
+    world: &SystemWorld,
+    command: &CompileCommand,
+    document: &Document,
+    path: &Path,
+) -> StrResult<()> {
+    let outputs = match command.format {
+        OutputFormat::Pdf => vec![command.output.clone()],
+        OutputFormat::Png | OutputFormat::Svg => page_paths(document, command)?,
+    };
+    let inputs = world.dependencies();
+
+    let text = if path.extension().map_or(false, |ext| ext == "json") {
+        let strings = |paths: &[PathBuf]| -> Vec<String> {
+            paths.iter().map(|path| path.to_string_lossy().into()).collect()
+        };
+        let json = serde_json::json!({
+            "outputs": strings(&outputs),
+            "inputs": strings(&inputs),
+        });
+        serde_json::to_string_pretty(&json).unwrap()
+    } else {
+        let mut rule = String::new();
+        for (i, output) in outputs.iter().enumerate() {
+            if i > 0 {
+                rule.push(' ');
+            }
+            rule.push_str(&make_escape(output));
+        }
+        rule.push(':');
+        for input in &inputs {
+            rule.push_str(" \\\n  ");
+            rule.push_str(&make_escape(input));
+        }
+        rule.push('\n');
+        rule
+    };
+
+    fs::write(path, text).map_err(|_| "failed to write dependency file")?;
+    Ok(())
+}
+
+/// Escape a path for use in a Makefile rule.
+fn make_escape(path: &Path) -> String {
+    let mut escaped = String::new();
+    for c in path.to_string_lossy().chars() {
+        match c {
+            ' ' | '#' => escaped.push('\\'),
+            '$' => escaped.push('$'),
+            _ => {}
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Determine the output paths for a format that produces one file per page.
+fn page_paths(document: &Document, command: &CompileCommand) -> StrResult<Vec<PathBuf>> {
+    // Determine whether we have a `{n}` numbering.
+    let template = command.output.to_str().unwrap_or_default();
+    let numbered = template.contains("{n}");
+    if !numbered && document.pages.len() > 1 {
+        Err("cannot export multiple pages without `{n}` in output path")?;
+    }
+
+    // Find a number width that accommodates all pages. For instance, the
+    // first page should be numbered "001" if there are between 100 and
+    // 999 pages.
+    let width = document.pages.len().to_string().len();
+    Ok((1..=document.pages.len())
+        .map(|n| {
+            if numbered {
+                let number = format!("{n:0width$}");
+                PathBuf::from(template.replace("{n}", &number))
+            } else {
+                command.output.clone()
+            }
+        })
+        .collect())
+}
+
+impl CompileCommand {
+    /// Whether the path is one of the files written by this command.
+    fn is_output(&self, path: &Path) -> bool {
+        let template = self.output.file_name().and_then(|name| name.to_str());
+        let Some((prefix, suffix)) = template.and_then(|t| t.split_once("{n}")) else {
+            return is_same_file(path, &self.output).unwrap_or(false);
+        };
+
+        let dir = |path: &Path| match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        is_same_file(dir(path), dir(&self.output)).unwrap_or(false)
+            && name
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(suffix))
+                .map_or(false, |n| !n.is_empty() && n.bytes().all(|c| c.is_ascii_digit()))
+    }
+}
+
 /// Clear the terminal and render the status message.
 fn status(command: &CompileCommand, status: Status) -> io::Result<()> {
     if !command.watch {
@@ -290,6 +963,13 @@ fn status(command: &CompileCommand, status: Status) -> io::Result<()> {
     w.reset()?;
     writeln!(w, " {output}")?;
 
+    if let Some(addr) = command.serve {
+        w.set_color(&color)?;
+        write!(w, "serving at")?;
+        w.reset()?;
+        writeln!(w, " http://{addr}")?;
+    }
+
     writeln!(w)?;
     writeln!(w, "[{timestamp}] {message}")?;
     writeln!(w)?;
@@ -322,20 +1002,65 @@ impl Status {
     }
 }
 
+/// How diagnostics are printed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum DiagnosticFormat {
+    /// Human-readable diagnostics with annotated source excerpts.
+    Human,
+    /// One line per diagnostic in the form `file:line:col: error: message`.
+    Short,
+    /// One JSON object per diagnostic and line.
+    Json,
+}
+
+impl FromStr for DiagnosticFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "human" => Self::Human,
+            "short" => Self::Short,
+            "json" => Self::Json,
+            _ => Err("expected human, short or json")?,
+        })
+    }
+}
+
 /// Print diagnostic messages to the terminal.
 fn print_diagnostics(
     world: &SystemWorld,
     errors: Vec<SourceError>,
+    format: DiagnosticFormat,
+) -> Result<(), codespan_reporting::files::Error> {
+    match format {
+        DiagnosticFormat::Human => print_human_diagnostics(world, errors),
+        DiagnosticFormat::Short => print_short_diagnostics(world, errors),
+        DiagnosticFormat::Json => print_json_diagnostics(world, errors),
+    }
+}
+
+/// Print diagnostic messages with annotated source excerpts.
+fn print_human_diagnostics(
+    world: &SystemWorld,
+    errors: Vec<SourceError>,
 ) -> Result<(), codespan_reporting::files::Error> {
     let mut w = StandardStream::stderr(ColorChoice::Always);
     let config = term::Config { tab_width: 2, ..Default::default() };
 
     for error in errors {
         // The main diagnostic.
-        let range = error.range(world);
-        let diag = Diagnostic::error()
-            .with_message(error.message)
-            .with_labels(vec![Label::primary(error.span.source(), range)]);
+        let diag = match error.severity {
+            Severity::Error => Diagnostic::error(),
+            Severity::Warning => Diagnostic::warning(),
+        }
+        .with_message(error.message.clone());
+
+        let diag = if error.span.is_detached() {
+            diag
+        } else {
+            let range = error.range(world);
+            diag.with_labels(vec![Label::primary(error.span.source(), range)])
+        };
 
         term::emit(&mut w, &config, world, &diag)?;
 
@@ -356,10 +1081,232 @@ fn print_diagnostics(
     Ok(())
 }
 
+/// Print one line per diagnostic message, as is common for compilers.
+fn print_short_diagnostics(
+    world: &SystemWorld,
+    errors: Vec<SourceError>,
+) -> Result<(), codespan_reporting::files::Error> {
+    let mut w = io::stderr().lock();
+    for error in errors {
+        let severity = severity_name(error.severity);
+        if error.span.is_detached() {
+            writeln!(w, "{severity}: {}", error.message)?;
+        } else {
+            let location =
+                DiagnosticLocation::new(world, error.span.source(), error.range(world));
+            writeln!(w, "{location}: {severity}: {}", error.message)?;
+        }
+
+        for point in error.trace {
+            let id = point.span.source();
+            let location =
+                DiagnosticLocation::new(world, id, world.source(id).range(point.span));
+            writeln!(w, "{location}: help: {}", point.v)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Print one JSON object per diagnostic message and line.
+fn print_json_diagnostics(
+    world: &SystemWorld,
+    errors: Vec<SourceError>,
+) -> Result<(), codespan_reporting::files::Error> {
+    let mut w = io::stderr().lock();
+    for error in errors {
+        let trace: Vec<_> = error
+            .trace
+            .iter()
+            .map(|point| {
+                let id = point.span.source();
+                let location = DiagnosticLocation::new(
+                    world,
+                    id,
+                    world.source(id).range(point.span),
+                );
+                let mut json = location.to_json();
+                json.insert("message".into(), point.v.to_string().into());
+                serde_json::Value::from(json)
+            })
+            .collect();
+
+        let mut json = if error.span.is_detached() {
+            serde_json::Map::new()
+        } else {
+            let location =
+                DiagnosticLocation::new(world, error.span.source(), error.range(world));
+            location.to_json()
+        };
+        json.insert("severity".into(), severity_name(error.severity).into());
+        json.insert("message".into(), error.message.as_str().into());
+        json.insert("trace".into(), trace.into());
+        writeln!(w, "{}", serde_json::Value::from(json))?;
+    }
+
+    Ok(())
+}
+
+/// The name of a severity in the machine-readable diagnostic formats.
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+/// A resolved location in a source file that a diagnostic refers to.
+struct DiagnosticLocation<'a> {
+    path: &'a Path,
+    range: Range<usize>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> DiagnosticLocation<'a> {
+    /// Resolve the line and column of the range's start.
+    fn new(world: &'a SystemWorld, id: SourceId, range: Range<usize>) -> Self {
+        let source = world.source(id);
+        let line = source.byte_to_line(range.start).unwrap_or_default();
+        let column = source.byte_to_column(range.start).unwrap_or_default();
+        Self {
+            path: source.path(),
+            range,
+            line: line + 1,
+            column: column + 1,
+        }
+    }
+
+    /// Convert the location into the fields of a JSON object.
+    fn to_json(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut map = serde_json::Map::new();
+        map.insert("file".into(), self.path.display().to_string().into());
+        map.insert("range".into(), serde_json::json!([self.range.start, self.range.end]));
+        map.insert("line".into(), self.line.into());
+        map.insert("column".into(), self.column.into());
+        map
+    }
+}
+
+impl Display for DiagnosticLocation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+/// Execute a query command.
+fn query(command: QueryCommand) -> StrResult<()> {
+    let root = determine_root(&command.input, command.root.as_deref());
+    let searcher = FontSearcher::system(&command.font_paths);
+    let mut world = SystemWorld::new(root, command.inputs.clone(), searcher);
+    world.max_iterations = command.max_iterations;
+    world.main = world.resolve(&command.input).map_err(|err| err.to_string())?;
+
+    let document = match typst::compile(&world) {
+        Ok((document, warnings)) => {
+            print_diagnostics(&world, warnings, command.diagnostic_format)
+                .map_err(|_| "failed to print diagnostics")?;
+            document
+        }
+        Err(errors) => {
+            print_diagnostics(&world, *errors, command.diagnostic_format)
+                .map_err(|_| "failed to print diagnostics")?;
+            Err("compilation failed")?
+        }
+    };
+
+    let matches =
+        query_document(&world, &document, &command.selector, command.field.as_deref())?;
+    let output = serde_json::to_string_pretty(&matches)
+        .map_err(|_| "failed to serialize query results")?;
+    println!("{output}");
+
+    Ok(())
+}
+
+/// Find the elements in a document that match a selector and convert them
+/// into JSON.
+///
+/// With a field, only its value is included for each element. Elements that
+/// don't have the field yield `null`, so that the results still line up with
+/// the matches.
+fn query_document(
+    world: &(dyn World + 'static),
+    document: &Document,
+    selector: &str,
+    field: Option<&str>,
+) -> StrResult<Vec<serde_json::Value>> {
+    // Evaluate the selector in the scope of the standard library.
+    let selector = typst::eval::eval_string(world.track(), selector, Span::detached())
+        .and_then(|value| value.cast::<Selector>().at(Span::detached()))
+        .map_err(|errors| {
+            let message = errors.first().map(|error| error.message.clone());
+            format!("failed to evaluate selector: {}", message.unwrap_or_default())
+        })?;
+
+    let introspector = Introspector::new(&document.pages);
+    let mut matches = vec![];
+    for elem in introspector.query(selector) {
+        if let Some(field) = field {
+            let value = elem.field(field);
+            matches.push(value.as_ref().map_or(serde_json::Value::Null, value_to_json));
+            continue;
+        }
+
+        let mut json = content_to_json(&elem);
+        if let Some(location) = elem.location() {
+            let position = introspector.position(location);
+            json.insert(
+                "location".into(),
+                serde_json::json!({
+                    "page": position.page.get(),
+                    "x": position.point.x.to_pt(),
+                    "y": position.point.y.to_pt(),
+                }),
+            );
+        }
+        matches.push(json.into());
+    }
+
+    Ok(matches)
+}
+
+/// Convert a value into JSON.
+///
+/// Values without a JSON equivalent are represented by their repr.
+fn value_to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::None => serde_json::Value::Null,
+        Value::Bool(v) => (*v).into(),
+        Value::Int(v) => (*v).into(),
+        Value::Float(v) => (*v).into(),
+        Value::Str(v) => v.as_str().into(),
+        Value::Label(label) => label.0.as_str().into(),
+        Value::Content(content) => content_to_json(content).into(),
+        Value::Array(array) => array.iter().map(value_to_json).collect(),
+        Value::Dict(dict) => dict
+            .iter()
+            .map(|(key, value)| (key.as_str().into(), value_to_json(value)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        _ => value.repr().as_str().into(),
+    }
+}
+
+/// Convert content into a JSON object with its element name and fields.
+fn content_to_json(content: &Content) -> serde_json::Map<String, serde_json::Value> {
+    let mut map = serde_json::Map::new();
+    map.insert("func".into(), content.func().name().into());
+    for (name, value) in content.fields() {
+        map.insert(name.as_str().into(), value_to_json(&value));
+    }
+    map
+}
+
 /// Execute a font listing command.
 fn fonts(command: FontsCommand) -> StrResult<()> {
     let mut searcher = FontSearcher::new();
-    searcher.search_system();
+    searcher.search(&command.font_paths);
     for (name, infos) in searcher.book.families() {
         println!("{name}");
         if command.variants {
@@ -373,6 +1320,55 @@ fn fonts(command: FontsCommand) -> StrResult<()> {
     Ok(())
 }
 
+/// Execute a formatting command.
+fn fmt(command: FmtCommand) -> StrResult<()> {
+    let mut files = vec![];
+    for path in &command.paths {
+        if path.is_dir() {
+            for entry in WalkDir::new(path).sort_by_file_name() {
+                let entry = entry.map_err(|err| err.to_string())?;
+                let path = entry.path();
+                if entry.file_type().is_file()
+                    && path.extension().map_or(false, |ext| ext == "typ")
+                {
+                    files.push(path.to_path_buf());
+                }
+            }
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let mut unformatted = 0;
+    for path in files {
+        let text = fs::read_to_string(&path)
+            .map_err(|_| format!("failed to read {}", path.display()))?;
+        let formatted = typst::syntax::format(&text)
+            .map_err(|err| format!("failed to format {}: {err}", path.display()))?;
+
+        if formatted == text {
+            continue;
+        }
+
+        if command.check {
+            println!("{}", path.display());
+            unformatted += 1;
+        } else {
+            fs::write(&path, formatted)
+                .map_err(|_| format!("failed to write {}", path.display()))?;
+        }
+    }
+
+    if unformatted > 0 {
+        Err(format!(
+            "{unformatted} file{} not formatted",
+            if unformatted > 1 { "s are" } else { " is" }
+        ))?;
+    }
+
+    Ok(())
+}
+
 /// A world that provides access to the operating system.
 struct SystemWorld {
     root: PathBuf,
@@ -381,8 +1377,11 @@ struct SystemWorld {
     fonts: Vec<FontSlot>,
     hashes: RefCell<HashMap<PathBuf, FileResult<PathHash>>>,
     paths: RefCell<HashMap<PathHash, PathSlot>>,
+    memory: HashMap<PathBuf, SourceId>,
     sources: FrozenVec<Box<Source>>,
     main: SourceId,
+    max_iterations: usize,
+    now: OnceCell<chrono::DateTime<chrono::Local>>,
 }
 
 /// Holds details about the location of a font and lazily the font itself.
@@ -393,29 +1392,43 @@ struct FontSlot {
 }
 
 /// Holds canonical data for all paths pointing to the same entity.
-#[derive(Default)]
 struct PathSlot {
+    path: PathBuf,
     source: OnceCell<FileResult<SourceId>>,
     buffer: OnceCell<FileResult<Buffer>>,
 }
 
-impl SystemWorld {
-    fn new(root: PathBuf) -> Self {
-        let mut searcher = FontSearcher::new();
-        searcher.search_system();
+impl PathSlot {
+    /// Create an empty slot for the first path that pointed to the entity.
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.into(),
+            source: OnceCell::new(),
+            buffer: OnceCell::new(),
+        }
+    }
 
-        #[cfg(feature = "embed-fonts")]
-        searcher.add_embedded();
+    /// Whether the entity was successfully read as a source file or buffer.
+    fn accessed(&self) -> bool {
+        matches!(self.source.get(), Some(Ok(_)))
+            || matches!(self.buffer.get(), Some(Ok(_)))
+    }
+}
 
+impl SystemWorld {
+    fn new(root: PathBuf, inputs: Dict, searcher: FontSearcher) -> Self {
         Self {
             root,
-            library: Prehashed::new(typst_library::build()),
+            library: Prehashed::new(typst_library::build_with_inputs(inputs)),
             book: Prehashed::new(searcher.book),
             fonts: searcher.fonts,
             hashes: RefCell::default(),
             paths: RefCell::default(),
+            memory: HashMap::new(),
             sources: FrozenVec::new(),
             main: SourceId::detached(),
+            max_iterations: 5,
+            now: OnceCell::new(),
         }
     }
 }
@@ -434,6 +1447,10 @@ impl World for SystemWorld {
     }
 
     fn resolve(&self, path: &Path) -> FileResult<SourceId> {
+        if let Some(&id) = self.memory.get(&path.normalize()) {
+            return Ok(id);
+        }
+
         self.slot(path)?
             .source
             .get_or_init(|| {
@@ -468,6 +1485,65 @@ impl World for SystemWorld {
             .get_or_init(|| read(path).map(Buffer::from))
             .clone()
     }
+
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        // All calls during one compilation should agree on the date.
+        let now = self.now.get_or_init(chrono::Local::now);
+        let naive = match offset {
+            None => now.naive_local(),
+            Some(hours) => {
+                let seconds = i32::try_from(hours.checked_mul(3600)?).ok()?;
+                let offset = chrono::FixedOffset::east_opt(seconds)?;
+                now.with_timezone(&offset).naive_local()
+            }
+        };
+
+        Datetime::from_ymd(
+            naive.year(),
+            naive.month().try_into().ok()?,
+            naive.day().try_into().ok()?,
+        )
+    }
+
+    fn package(&self, spec: &PackageSpec) -> FileResult<PathBuf> {
+        // Only packages in the `local` namespace are supported for now.
+        let dir = (spec.namespace == "local").then(packages_dir).flatten().map(|dir| {
+            dir.join(spec.namespace.as_str())
+                .join(spec.name.as_str())
+                .join(spec.version.to_string())
+        });
+
+        match dir {
+            Some(dir) if dir.is_dir() => Ok(dir),
+            _ => Err(FileError::PackageNotFound(spec.to_string().into())),
+        }
+    }
+
+    fn root_of(&self, id: SourceId) -> PathBuf {
+        package_root(World::source(self, id).path()).unwrap_or_else(|| self.root.clone())
+    }
+
+    fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+}
+
+/// The directory in which local packages are stored, e.g.
+/// `~/.local/share/typst/packages` on Linux.
+fn packages_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("typst").join("packages"))
+}
+
+/// Determine the root directory of the package a path belongs to, if any.
+///
+/// Package roots have the form `{packages}/{namespace}/{name}/{version}`.
+fn package_root(path: &Path) -> Option<PathBuf> {
+    let mut root = packages_dir()?;
+    let mut components = path.strip_prefix(&root).ok()?.components();
+    for _ in 0..3 {
+        root.push(components.next()?);
+    }
+    Some(root)
 }
 
 impl SystemWorld {
@@ -486,7 +1562,7 @@ impl SystemWorld {
         }?;
 
         Ok(std::cell::RefMut::map(self.paths.borrow_mut(), |paths| {
-            paths.entry(hash).or_default()
+            paths.entry(hash).or_insert_with(|| PathSlot::new(path))
         }))
     }
 
@@ -522,10 +1598,60 @@ impl SystemWorld {
                 .map_or(false, |hash| self.paths.borrow().contains_key(&hash))
     }
 
+    /// The paths of all files that were read since the last reset, including
+    /// the fonts that were loaded.
+    fn dependencies(&self) -> Vec<PathBuf> {
+        let files = self.paths.borrow();
+        let fonts = self
+            .fonts
+            .iter()
+            .filter(|slot| !slot.path.as_os_str().is_empty())
+            .filter(|slot| matches!(slot.font.get(), Some(Some(_))))
+            .map(|slot| &slot.path);
+
+        let mut deps: Vec<PathBuf> = files
+            .values()
+            .filter(|slot| slot.accessed())
+            .map(|slot| &slot.path)
+            .chain(fonts)
+            .cloned()
+            .collect();
+
+        deps.sort();
+        deps.dedup();
+        deps
+    }
+
     fn reset(&mut self) {
         self.sources.as_mut().clear();
         self.hashes.borrow_mut().clear();
         self.paths.borrow_mut().clear();
+        self.memory.clear();
+        self.now.take();
+    }
+
+    /// Replace the text of the source file at the given path, e.g. with the
+    /// unsaved contents of an editor buffer.
+    ///
+    /// Files that don't exist on disk are kept in memory until the next reset.
+    fn update(&mut self, path: &Path, text: String) -> FileResult<SourceId> {
+        let id = match self.resolve(path) {
+            Ok(id) => id,
+            Err(FileError::NotFound(_)) => {
+                let id = self.insert(path, String::new());
+                self.memory.insert(path.normalize(), id);
+                id
+            }
+            Err(err) => return Err(err),
+        };
+
+        self.sources.as_mut()[id.into_u16() as usize].replace(text);
+        Ok(id)
+    }
+
+    /// Replace a byte range in a source file and incrementally reparse it.
+    fn edit(&mut self, id: SourceId, replace: Range<usize>, with: &str) -> Range<usize> {
+        self.sources.as_mut()[id.into_u16() as usize].edit(replace, with)
     }
 }
 
@@ -579,11 +1705,7 @@ impl<'a> codespan_reporting::files::Files<'a> for SystemWorld {
             })
     }
 
-    fn line_range(
-        &'a self,
-        id: SourceId,
-        given: usize,
-    ) -> CodespanResult<std::ops::Range<usize>> {
+    fn line_range(&'a self, id: SourceId, given: usize) -> CodespanResult<Range<usize>> {
         let source = World::source(self, id);
         source
             .line_to_range(given)
@@ -612,12 +1734,53 @@ impl<'a> codespan_reporting::files::Files<'a> for SystemWorld {
 struct FontSearcher {
     book: FontBook,
     fonts: Vec<FontSlot>,
+    cache: FontCache,
+    dirty: bool,
 }
 
 impl FontSearcher {
     /// Create a new, empty system searcher.
     fn new() -> Self {
-        Self { book: FontBook::new(), fonts: vec![] }
+        Self {
+            book: FontBook::new(),
+            fonts: vec![],
+            cache: FontCache::load(),
+            dirty: false,
+        }
+    }
+
+    /// Create a searcher that found the fonts in the given directories, the
+    /// system font directories and, if enabled, the embedded fonts.
+    fn system(font_paths: &[PathBuf]) -> Self {
+        let mut searcher = Self::new();
+        searcher.search(font_paths);
+
+        #[cfg(feature = "embed-fonts")]
+        searcher.add_embedded();
+
+        searcher
+    }
+
+    /// Search for fonts in the given directories and the system font
+    /// directories and update the font cache.
+    fn search(&mut self, font_paths: &[PathBuf]) {
+        for path in font_paths {
+            self.search_dir(path);
+        }
+
+        self.search_system();
+
+        // Forget about fonts that were removed or changed since they were
+        // cached, but keep those that just weren't searched this time. Files
+        // that were searched are already known to be fresh.
+        let checked = std::mem::take(&mut self.cache.checked);
+        let stale = self.cache.files.len();
+        self.cache
+            .files
+            .retain(|path, file| checked.contains(path) || file.is_fresh(path));
+        if self.dirty || self.cache.files.len() != stale {
+            self.cache.save();
+        }
     }
 
     /// Add fonts that are embedded in the binary.
@@ -705,19 +1868,211 @@ impl FontSearcher {
     }
 
     /// Index the fonts in the file at the given path.
+    ///
+    /// The fonts are only parsed if the file isn't cached or changed since it
+    /// was cached.
     fn search_file(&mut self, path: impl AsRef<Path>) {
         let path = path.as_ref();
-        if let Ok(file) = File::open(path) {
-            if let Ok(mmap) = unsafe { Mmap::map(&file) } {
-                for (i, info) in FontInfo::iter(&mmap).enumerate() {
-                    self.book.push(info);
-                    self.fonts.push(FontSlot {
-                        path: path.into(),
-                        index: i as u32,
-                        font: OnceCell::new(),
-                    });
+        let infos = match self.cache.files.get(path) {
+            Some(file) if file.is_fresh(path) => file.infos.clone(),
+            cached => {
+                if cached.is_some() {
+                    self.cache.files.remove(path);
+                    self.dirty = true;
                 }
+
+                let Ok(metadata) = fs::metadata(path) else { return };
+                let Ok(file) = File::open(path) else { return };
+                let Ok(mmap) = (unsafe { Mmap::map(&file) }) else { return };
+                let infos: Vec<_> = FontInfo::iter(&mmap).collect();
+                let (size, mtime) = file_stamp(&metadata);
+                self.cache.files.insert(
+                    path.into(),
+                    CachedFontFile { size, mtime, infos: infos.clone() },
+                );
+                self.dirty = true;
+                infos
             }
+        };
+
+        self.cache.checked.insert(path.into());
+
+        for (i, info) in infos.into_iter().enumerate() {
+            self.book.push(info);
+            self.fonts.push(FontSlot {
+                path: path.into(),
+                index: i as u32,
+                font: OnceCell::new(),
+            });
+        }
+    }
+}
+
+/// The fonts in all font files discovered so far, stored on disk to speed up
+/// font discovery.
+#[derive(Default, Serialize, Deserialize)]
+struct FontCache {
+    /// The version of the CLI that wrote the cache.
+    version: String,
+    /// The cached fonts by file path.
+    files: HashMap<PathBuf, CachedFontFile>,
+    /// The files whose cache entries were checked during the current search.
+    #[serde(skip)]
+    checked: HashSet<PathBuf>,
+}
+
+/// The fonts in a font file.
+#[derive(Serialize, Deserialize)]
+struct CachedFontFile {
+    /// The size of the file in bytes.
+    size: u64,
+    /// The modification time of the file in nanoseconds since the Unix epoch.
+    mtime: u64,
+    /// The fonts in the file.
+    infos: Vec<FontInfo>,
+}
+
+impl FontCache {
+    /// The location of the cache, e.g. `~/.cache/typst/fonts.json` on Linux.
+    fn path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("typst").join("fonts.json"))
+    }
+
+    /// Load the cache, which is empty if it doesn't exist, is corrupted or
+    /// was written by another version of the CLI.
+    fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|data| serde_json::from_slice::<Self>(&data).ok())
+            .filter(|cache| cache.version == env!("TYPST_VERSION"))
+            .unwrap_or_default()
+    }
+
+    /// Write the cache. Failure is not an error since the cache is only an
+    /// optimization.
+    ///
+    /// The cache is written to a temporary file next to it first and then
+    /// moved into place, so that concurrent processes never read a partially
+    /// written cache.
+    fn save(&mut self) {
+        let Some(path) = Self::path() else { return };
+        self.version = env!("TYPST_VERSION").into();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).ok();
+        }
+        let Ok(data) = serde_json::to_vec(self) else { return };
+        let temp = path.with_extension(format!("json.{}.tmp", process::id()));
+        if fs::write(&temp, data).is_err() || fs::rename(&temp, &path).is_err() {
+            fs::remove_file(&temp).ok();
         }
     }
 }
+
+impl CachedFontFile {
+    /// Whether the file at the path still has the cached size and
+    /// modification time.
+    fn is_fresh(&self, path: &Path) -> bool {
+        fs::metadata(path)
+            .map_or(false, |metadata| file_stamp(&metadata) == (self.size, self.mtime))
+    }
+}
+
+/// The size and modification time of a file, which identify its contents for
+/// the font cache.
+fn file_stamp(metadata: &fs::Metadata) -> (u64, u64) {
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos() as u64);
+    (metadata.len(), mtime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a world that neither touches the font cache nor searches the
+    /// system fonts.
+    fn world(root: PathBuf) -> SystemWorld {
+        #[allow(unused_mut)]
+        let mut searcher = FontSearcher {
+            book: FontBook::new(),
+            fonts: vec![],
+            cache: FontCache::default(),
+            dirty: false,
+        };
+
+        #[cfg(feature = "embed-fonts")]
+        searcher.add_embedded();
+
+        SystemWorld::new(root, Dict::new(), searcher)
+    }
+
+    /// Compile a document and query it.
+    fn query(text: &str, selector: &str, field: Option<&str>) -> Vec<serde_json::Value> {
+        let hash = typst::util::hash128(&(text, selector, field));
+        let dir =
+            std::env::temp_dir().join(format!("typst-query-{}-{hash}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("main.typ");
+        fs::write(&path, text).unwrap();
+
+        let mut world = world(dir.clone());
+        world.main = world.resolve(&path).unwrap();
+        let (document, _) = typst::compile(&world).unwrap();
+        let result = query_document(&world, &document, selector, field);
+        fs::remove_dir_all(&dir).unwrap();
+        result.unwrap()
+    }
+
+    #[test]
+    fn test_query_elements() {
+        let matches = query("= Intro <intro>\n== Details", "heading", None);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0]["func"], "heading");
+        assert_eq!(matches[0]["label"], "intro");
+        assert_eq!(matches[0]["level"], 1);
+        assert_eq!(matches[0]["location"]["page"], 1);
+        assert_eq!(matches[1]["level"], 2);
+
+        let matches = query("= Intro <intro>\n== Details", "<intro>", None);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0]["func"], "heading");
+    }
+
+    #[test]
+    fn test_query_field() {
+        let text = "= Intro <intro>\n== Details";
+        assert_eq!(query(text, "heading", Some("level")), [1, 2]);
+
+        // Elements without the field yield null.
+        assert_eq!(
+            query(text, "heading", Some("label")),
+            [serde_json::Value::from("intro"), serde_json::Value::Null]
+        );
+    }
+
+    #[test]
+    fn test_query_invalid_selector() {
+        let world = world(std::env::temp_dir());
+        let error = query_document(&world, &Document::default(), "1 +", None);
+        assert!(error.unwrap_err().starts_with("failed to evaluate selector"));
+    }
+
+    #[test]
+    fn test_update_in_memory() {
+        // The file is never written to disk.
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("typst-unsaved-{}.typ", process::id()));
+        let mut world = world(dir);
+        assert!(world.resolve(&path).is_err());
+
+        // Files that don't exist on disk are kept in memory until a reset.
+        let id = world.update(&path, "#let x = 1".into()).unwrap();
+        assert_eq!(world.resolve(&path), Ok(id));
+        assert_eq!(World::source(&world, id).text(), "#let x = 1");
+        world.reset();
+        assert!(world.resolve(&path).is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! A live preview server for watch mode.
+//!
+//! Serves an HTML page that shows the pages of the document as SVG images and
+//! updates them through server-sent events after each compilation. Clicks on
+//! pages are sent back to the watch loop.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use typst::diag::StrResult;
+use typst::doc::Document;
+use typst::geom::{Abs, Point};
+use typst::util::hash128;
+
+use super::Message;
+
+/// How long writing to a client may block before it is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The page that displays the preview.
+const HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Typst Preview</title>
+<style>
+  body { margin: 0; padding: 16px 0; background: #e5e5e5; }
+  .page { width: fit-content; margin: 0 auto 16px; background: white;
+          box-shadow: 0 2px 8px rgba(0, 0, 0, 0.2); cursor: pointer; }
+  .page > svg { display: block; }
+</style>
+</head>
+<body>
+<script>
+  const pages = [];
+  const events = new EventSource("/events");
+  events.addEventListener("update", (event) => {
+    const update = JSON.parse(event.data);
+    while (pages.length > update.count) pages.pop().remove();
+    while (pages.length < update.count) {
+      const index = pages.length;
+      const page = document.createElement("div");
+      page.className = "page";
+      page.addEventListener("click", (click) => {
+        const rect = page.getBoundingClientRect();
+        const x = (click.clientX - rect.left) / rect.width * page.dataset.width;
+        const y = (click.clientY - rect.top) / rect.height * page.dataset.height;
+        fetch(`/click?page=${index}&x=${x}&y=${y}`, { method: "POST" });
+      });
+      document.body.appendChild(page);
+      pages.push(page);
+    }
+    for (const changed of update.pages) {
+      const page = pages[changed.index];
+      page.dataset.width = changed.width;
+      page.dataset.height = changed.height;
+      page.innerHTML = changed.svg;
+    }
+  });
+  events.addEventListener("jump", (event) => {
+    const jump = JSON.parse(event.data);
+    const page = pages[jump.page];
+    if (!page) return;
+    const rect = page.getBoundingClientRect();
+    const y = rect.top + window.scrollY + jump.y / page.dataset.height * rect.height;
+    window.scrollTo({ top: y - 16, behavior: "smooth" });
+  });
+</script>
+</body>
+</html>
+"#;
+
+/// Serves a live preview of a document.
+pub struct Server {
+    state: Arc<Mutex<State>>,
+}
+
+/// The state shared between the server's threads.
+#[derive(Default)]
+struct State {
+    /// The most recently rendered pages.
+    pages: Vec<Page>,
+    /// The connections that receive updates.
+    clients: Vec<TcpStream>,
+}
+
+/// A rendered page.
+#[derive(Serialize)]
+struct Page {
+    /// The index of the page in the document.
+    index: usize,
+    /// The width of the page in points.
+    width: f64,
+    /// The height of the page in points.
+    height: f64,
+    /// The page as an SVG image.
+    svg: String,
+    /// The hash of the frame the page was rendered from.
+    #[serde(skip)]
+    hash: u128,
+}
+
+/// A set of pages that changed.
+#[derive(Serialize)]
+struct Update<'a> {
+    /// The number of pages in the document.
+    count: usize,
+    /// The pages that changed.
+    pages: Vec<&'a Page>,
+}
+
+impl Server {
+    /// Start serving at the given address. Clicks on pages are sent to the
+    /// channel.
+    pub fn start(addr: SocketAddr, tx: Sender<Message>) -> StrResult<Self> {
+        let listener = TcpListener::bind(addr)
+            .map_err(|err| format!("failed to serve at {addr} ({err})"))?;
+
+        let state = Arc::new(Mutex::new(State::default()));
+        let shared = state.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = shared.clone();
+                let tx = tx.clone();
+                thread::spawn(move || handle(stream, &state, &tx));
+            }
+        });
+
+        Ok(Self { state })
+    }
+
+    /// Render the pages of a document whose frames changed and push them to
+    /// all connected clients.
+    pub fn update(&self, document: &Document) {
+        let mut state = self.state.lock().unwrap();
+        state.pages.truncate(document.pages.len());
+
+        let mut changed = vec![];
+        for (index, frame) in document.pages.iter().enumerate() {
+            let hash = hash128(frame);
+            if state.pages.get(index).map_or(false, |page| page.hash == hash) {
+                continue;
+            }
+
+            let page = Page {
+                index,
+                width: frame.width().to_pt(),
+                height: frame.height().to_pt(),
+                // Prefix the ids so that they don't clash between the pages
+                // inlined into the same HTML page.
+                svg: typst::export::svg_with_prefix(frame, &format!("p{index}-")),
+                hash,
+            };
+
+            match state.pages.get_mut(index) {
+                Some(slot) => *slot = page,
+                None => state.pages.push(page),
+            }
+
+            changed.push(index);
+        }
+
+        let update = Update {
+            count: state.pages.len(),
+            pages: changed.iter().map(|&i| &state.pages[i]).collect(),
+        };
+
+        let event = event("update", &update);
+        drop(state);
+        self.broadcast(&event);
+    }
+
+    /// Scroll all clients to a position in the document.
+    pub fn scroll(&self, page: usize, y: Abs) {
+        let event = event("jump", &serde_json::json!({ "page": page, "y": y.to_pt() }));
+        self.broadcast(&event);
+    }
+
+    /// Send an event to all clients and drop those that failed to receive it.
+    ///
+    /// The clients are taken out of the shared state while writing so that
+    /// a slow client doesn't block new connections.
+    fn broadcast(&self, event: &str) {
+        let mut clients = std::mem::take(&mut self.state.lock().unwrap().clients);
+        clients.retain_mut(|client| client.write_all(event.as_bytes()).is_ok());
+        self.state.lock().unwrap().clients.extend(clients);
+    }
+}
+
+/// Handle a single HTTP request.
+fn handle(mut stream: TcpStream, state: &Mutex<State>, tx: &Sender<Message>) {
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    if reader.read_line(&mut request).is_err() {
+        return;
+    }
+
+    // Skip the headers.
+    let mut line = String::new();
+    while reader.read_line(&mut line).map_or(false, |n| n > 0) && !line.trim().is_empty()
+    {
+        line.clear();
+    }
+
+    let mut parts = request.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    match (method, path) {
+        ("GET", "/") => {
+            respond(&mut stream, "200 OK", "text/html; charset=utf-8", HTML);
+        }
+        ("GET", "/events") => {
+            let header = "HTTP/1.1 200 OK\r\n\
+                          Content-Type: text/event-stream\r\n\
+                          Cache-Control: no-cache\r\n\r\n";
+
+            // Send all pages to the new client before it receives updates.
+            let mut state = state.lock().unwrap();
+            let update = Update {
+                count: state.pages.len(),
+                pages: state.pages.iter().collect(),
+            };
+            let event = event("update", &update);
+            if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok()
+                && stream.write_all(header.as_bytes()).is_ok()
+                && stream.write_all(event.as_bytes()).is_ok()
+            {
+                state.clients.push(stream);
+            }
+        }
+        ("POST", "/click") => match parse_click(query) {
+            Some((page, point)) => {
+                tx.send(Message::Click(page, point)).ok();
+                respond(&mut stream, "204 No Content", "text/plain", "");
+            }
+            None => {
+                respond(&mut stream, "400 Bad Request", "text/plain", "invalid click")
+            }
+        },
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found"),
+    }
+}
+
+/// Parse the page index and position of a click from a query string.
+fn parse_click(query: &str) -> Option<(usize, Point)> {
+    let (mut page, mut x, mut y) = (None, None, None);
+    for pair in query.split('&') {
+        match pair.split_once('=')? {
+            ("page", value) => page = value.parse().ok(),
+            ("x", value) => x = value.parse().ok(),
+            ("y", value) => y = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some((page?, Point::new(Abs::pt(x?), Abs::pt(y?))))
+}
+
+/// Write a complete HTTP response.
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n\
+         {body}",
+        body.len(),
+    );
+    stream.write_all(response.as_bytes()).ok();
+}
+
+/// Format a server-sent event with JSON data.
+fn event<T: Serialize>(name: &str, data: &T) -> String {
+    let data = serde_json::to_string(data).unwrap();
+    format!("event: {name}\ndata: {data}\n\n")
+}
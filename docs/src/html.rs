@@ -2,6 +2,7 @@ use comemo::Prehashed;
 use md::escape::escape_html;
 use pulldown_cmark as md;
 use typst::diag::FileResult;
+use typst::eval::Datetime;
 use typst::font::{Font, FontBook};
 use typst::geom::{Point, Size};
 use typst::syntax::{Source, SourceId};
@@ -308,7 +309,7 @@ fn code_block(resolver: &dyn Resolver, lang: &str, text: &str) -> Html {
     let source = Source::new(SourceId::from_u16(0), Path::new("main.typ"), compile);
     let world = DocWorld(source);
     let mut frames = match typst::compile(&world) {
-        Ok(doc) => doc.pages,
+        Ok((doc, _)) => doc.pages,
         Err(err) => {
             let msg = &err[0].message;
             panic!("while trying to compile:\n{text}:\n\nerror: {msg}");
@@ -363,4 +364,8 @@ impl World for DocWorld {
             .contents()
             .into())
     }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        Some(Datetime::from_ymd(1970, 1, 1).unwrap())
+    }
 }
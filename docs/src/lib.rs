@@ -744,8 +744,11 @@ const TYPE_ORDER: &[&str] = &[
     "fraction",
     "color",
     "string",
+    "bytes",
     "regex",
     "label",
+    "datetime",
+    "duration",
     "content",
     "array",
     "dictionary",
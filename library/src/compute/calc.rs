@@ -19,12 +19,22 @@ pub fn module() -> Module {
     scope.define("asin", asin);
     scope.define("acos", acos);
     scope.define("atan", atan);
+    scope.define("atan2", atan2);
     scope.define("sinh", sinh);
     scope.define("cosh", cosh);
     scope.define("tanh", tanh);
     scope.define("log", log);
+    scope.define("ln", ln);
+    scope.define("exp", exp);
+    scope.define("fact", fact);
+    scope.define("perm", perm);
+    scope.define("binom", binom);
+    scope.define("gcd", gcd);
+    scope.define("lcm", lcm);
     scope.define("floor", floor);
     scope.define("ceil", ceil);
+    scope.define("trunc", trunc);
+    scope.define("fract", fract);
     scope.define("round", round);
     scope.define("clamp", clamp);
     scope.define("min", min);
@@ -32,9 +42,13 @@ pub fn module() -> Module {
     scope.define("even", even);
     scope.define("odd", odd);
     scope.define("mod", mod_);
+    scope.define("rem", rem);
+    scope.define("quo", quo);
+    scope.define("rng", rng);
     scope.define("inf", Value::Float(f64::INFINITY));
     scope.define("nan", Value::Float(f64::NAN));
     scope.define("pi", Value::Float(std::f64::consts::PI));
+    scope.define("tau", Value::Float(std::f64::consts::TAU));
     scope.define("e", Value::Float(std::f64::consts::E));
     Module::new("calc").with_scope(scope)
 }
@@ -64,7 +78,7 @@ struct ToAbs(Value);
 
 cast_from_value! {
     ToAbs,
-    v: i64 => Self(Value::Int(v.abs())),
+    v: i64 => Self(Value::Int(v.checked_abs().ok_or_else(too_large)?)),
     v: f64 => Self(Value::Float(v.abs())),
     v: Length => Self(Value::Length(v.try_abs()
         .ok_or_else(|| "cannot take absolute value of this length")?)),
@@ -100,7 +114,31 @@ pub fn pow(
             bail!(exponent.span, "exponent must be non-negative");
         }
     };
-    base.apply2(exponent, |a, b| a.pow(b as u32), f64::powf)
+    match (base, exponent) {
+        (Num::Int(a), Num::Int(b)) => {
+            Value::Int(a.checked_pow(b as u32).ok_or_else(too_large).at(args.span)?)
+        }
+        (a, b) => Value::Float(a.float().powf(b.float())),
+    }
+}
+
+/// Raise Euler's number to some exponent.
+///
+/// ## Example
+/// ```example
+/// #calc.exp(1) \
+/// #calc.exp(2.5)
+/// ```
+///
+/// Display: Exponential
+/// Category: calculate
+/// Returns: float
+#[func]
+pub fn exp(
+    /// The exponent of the power.
+    exponent: Num,
+) -> Value {
+    Value::Float(exponent.float().exp())
 }
 
 /// Calculate the square root of a number.
@@ -270,6 +308,30 @@ pub fn atan(
     Value::Angle(Angle::rad(value.float().atan()))
 }
 
+/// Calculate the four-quadrant arctangent of a coordinate.
+///
+/// The four-quadrant arctangent takes the signs of both coordinates into
+/// account and thus returns an angle between -180° and 180°.
+///
+/// ## Example
+/// ```example
+/// #calc.atan2(1, 1) \
+/// #calc.atan2(-2, -3)
+/// ```
+///
+/// Display: Four-quadrant Arctangent
+/// Category: calculate
+/// Returns: angle
+#[func]
+pub fn atan2(
+    /// The X coordinate.
+    x: Num,
+    /// The Y coordinate.
+    y: Num,
+) -> Value {
+    Value::Angle(Angle::rad(f64::atan2(y.float(), x.float())))
+}
+
 /// Calculate the hyperbolic sine of an angle.
 ///
 /// When called with an integer or a float, they will be interpreted as radians.
@@ -375,6 +437,156 @@ pub fn log(
     })
 }
 
+/// Calculate the natural logarithm of a number.
+///
+/// ## Example
+/// ```example
+/// #calc.ln(calc.e) \
+/// #calc.ln(10)
+/// ```
+///
+/// Display: Natural Logarithm
+/// Category: calculate
+/// Returns: float
+#[func]
+pub fn ln(
+    /// The number whose logarithm to calculate. Must be strictly positive.
+    value: Spanned<Num>,
+) -> Value {
+    if value.v.float() <= 0.0 {
+        bail!(value.span, "value must be strictly positive");
+    }
+    Value::Float(value.v.float().ln())
+}
+
+/// Calculate the factorial of a number.
+///
+/// ## Example
+/// ```example
+/// #calc.fact(5)
+/// ```
+///
+/// Display: Factorial
+/// Category: calculate
+/// Returns: integer
+#[func]
+pub fn fact(
+    /// The number whose factorial to calculate. Must be non-negative.
+    number: Spanned<i64>,
+) -> Value {
+    let n = non_negative(number)?;
+    Value::Int(product_range(1, n).ok_or_else(too_large).at(args.span)?)
+}
+
+/// Calculate a permutation.
+///
+/// Returns the number of ordered selections of `numbers` elements out of a set
+/// of `base` elements, i.e. `base! / (base - numbers)!`.
+///
+/// ## Example
+/// ```example
+/// #calc.perm(10, 5)
+/// ```
+///
+/// Display: Permutation
+/// Category: calculate
+/// Returns: integer
+#[func]
+pub fn perm(
+    /// The base number. Must be non-negative.
+    base: Spanned<i64>,
+    /// The number of permutations. Must be non-negative.
+    numbers: Spanned<i64>,
+) -> Value {
+    let base = non_negative(base)?;
+    let numbers = non_negative(numbers)?;
+    if base < numbers {
+        return Ok(Value::Int(0));
+    }
+
+    Value::Int(
+        product_range(base - numbers + 1, base)
+            .ok_or_else(too_large)
+            .at(args.span)?,
+    )
+}
+
+/// Calculate a binomial coefficient.
+///
+/// Returns the number of unordered selections of `k` elements out of a set of
+/// `n` elements.
+///
+/// ## Example
+/// ```example
+/// #calc.binom(10, 5)
+/// ```
+///
+/// Display: Binomial
+/// Category: calculate
+/// Returns: integer
+#[func]
+pub fn binom(
+    /// The upper coefficient. Must be non-negative.
+    n: Spanned<i64>,
+    /// The lower coefficient. Must be non-negative.
+    k: Spanned<i64>,
+) -> Value {
+    let n = non_negative(n)?;
+    let k = non_negative(k)?;
+    Value::Int(binomial(n, k).ok_or_else(too_large).at(args.span)?)
+}
+
+/// Calculate the greatest common divisor of two integers.
+///
+/// ## Example
+/// ```example
+/// #calc.gcd(7, 42)
+/// ```
+///
+/// Display: Greatest Common Divisor
+/// Category: calculate
+/// Returns: integer
+#[func]
+pub fn gcd(
+    /// The first integer.
+    a: i64,
+    /// The second integer.
+    b: i64,
+) -> Value {
+    let gcd = i64::try_from(gcd_u64(a.unsigned_abs(), b.unsigned_abs()))
+        .map_err(|_| too_large())
+        .at(args.span)?;
+    Value::Int(gcd)
+}
+
+/// Calculate the least common multiple of two integers.
+///
+/// ## Example
+/// ```example
+/// #calc.lcm(96, 13)
+/// ```
+///
+/// Display: Least Common Multiple
+/// Category: calculate
+/// Returns: integer
+#[func]
+pub fn lcm(
+    /// The first integer.
+    a: i64,
+    /// The second integer.
+    b: i64,
+) -> Value {
+    let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+    let lcm = if a == 0 || b == 0 {
+        Some(0)
+    } else {
+        (a / gcd_u64(a, b))
+            .checked_mul(b)
+            .and_then(|lcm| i64::try_from(lcm).ok())
+    };
+    Value::Int(lcm.ok_or_else(too_large).at(args.span)?)
+}
+
 /// Round a number down to the nearest integer.
 ///
 /// If the number is already an integer, it is returned unchanged.
@@ -425,6 +637,55 @@ pub fn ceil(
     }
 }
 
+/// Truncate a number to its integer part.
+///
+/// If the number is already an integer, it is returned unchanged.
+///
+/// ## Example
+/// ```example
+/// #assert(calc.trunc(3) == 3)
+/// #assert(calc.trunc(-3.7) == -3)
+/// #calc.trunc(15.9)
+/// ```
+///
+/// Display: Truncate
+/// Category: calculate
+/// Returns: integer
+#[func]
+pub fn trunc(
+    /// The number to truncate.
+    value: Num,
+) -> Value {
+    match value {
+        Num::Int(n) => Value::Int(n),
+        Num::Float(n) => Value::Int(float_to_int(n.trunc()).at(args.span)?),
+    }
+}
+
+/// Return the fractional part of a number.
+///
+/// If the number is an integer, it returns `0`.
+///
+/// ## Example
+/// ```example
+/// #assert(calc.fract(3) == 0)
+/// #calc.fract(-3.1)
+/// ```
+///
+/// Display: Fractional
+/// Category: calculate
+/// Returns: integer or float
+#[func]
+pub fn fract(
+    /// The number whose fractional part to calculate.
+    value: Num,
+) -> Value {
+    match value {
+        Num::Int(_) => Value::Int(0),
+        Num::Float(n) => Value::Float(n.fract()),
+    }
+}
+
 /// Round a number to the nearest integer.
 ///
 /// Optionally, a number of decimal places can be specified.
@@ -618,7 +879,308 @@ pub fn mod_(
     if divisor.v.float() == 0.0 {
         bail!(divisor.span, "divisor must not be zero");
     }
-    dividend.apply2(divisor.v, Rem::rem, Rem::rem)
+    dividend.apply2(divisor.v, i64::wrapping_rem, Rem::rem)
+}
+
+/// Calculate the remainder of two numbers.
+///
+/// The result has the same sign as the dividend.
+///
+/// ## Example
+/// ```example
+/// #calc.rem(20, 6) \
+/// #calc.rem(-7, 3) \
+/// #calc.rem(1.75, 0.5)
+/// ```
+///
+/// Display: Remainder
+/// Category: calculate
+/// Returns: integer or float
+#[func]
+pub fn rem(
+    /// The dividend of the remainder.
+    dividend: Num,
+    /// The divisor of the remainder.
+    divisor: Spanned<Num>,
+) -> Value {
+    if divisor.v.float() == 0.0 {
+        bail!(divisor.span, "divisor must not be zero");
+    }
+    dividend.apply2(divisor.v, i64::wrapping_rem, Rem::rem)
+}
+
+/// Calculate the quotient of two numbers, rounded down.
+///
+/// ## Example
+/// ```example
+/// #calc.quo(14, 5) \
+/// #calc.quo(-7, 2) \
+/// #calc.quo(3.0, 0.5)
+/// ```
+///
+/// Display: Quotient
+/// Category: calculate
+/// Returns: integer
+#[func]
+pub fn quo(
+    /// The dividend of the quotient.
+    dividend: Num,
+    /// The divisor of the quotient.
+    divisor: Spanned<Num>,
+) -> Value {
+    if divisor.v.float() == 0.0 {
+        bail!(divisor.span, "divisor must not be zero");
+    }
+
+    Value::Int(match (dividend, divisor.v) {
+        (Num::Int(a), Num::Int(b)) => {
+            let quo = a.checked_div(b).ok_or_else(too_large).at(args.span)?;
+            if a % b != 0 && (a < 0) != (b < 0) {
+                quo - 1
+            } else {
+                quo
+            }
+        }
+        (a, b) => float_to_int((a.float() / b.float()).floor()).at(args.span)?,
+    })
+}
+
+/// Create a seeded random number generator.
+///
+/// Typst code is pure: Calling a function with the same arguments always
+/// yields the same result. Therefore, there is no global source of randomness.
+/// Instead, you create a generator from a seed and thread it through your code
+/// explicitly. Each method returns an array with its result and the advanced
+/// generator, which you should use for subsequent calls. The same seed always
+/// yields the same sequence of results.
+///
+/// ## Example
+/// ```example
+/// #let rng = calc.rng(seed: 42)
+/// #let (a, rng) = rng.int(1, 7)
+/// #let (b, rng) = rng.int(1, 7)
+/// I rolled a #a and a #b.
+///
+/// #let (order, rng) = rng.shuffle(("Alice", "Bob", "Carol"))
+/// The order is #order.join(", ").
+/// ```
+///
+/// ## Methods
+/// ### int()
+/// Generate a random integer in a range.
+///
+/// - start: integer (positional, required)
+///   The start of the range (inclusive).
+/// - end: integer (positional, required)
+///   The end of the range (exclusive). Must be greater than the start.
+///
+/// - returns: array
+///
+/// ### float()
+/// Generate a random float between `{0.0}` (inclusive) and `{1.0}`
+/// (exclusive).
+///
+/// - returns: array
+///
+/// ### choice()
+/// Pick a random item from an array.
+///
+/// - array: array (positional, required)
+///   The array to pick from. Must not be empty.
+///
+/// - returns: array
+///
+/// ### shuffle()
+/// Randomly reorder the items of an array.
+///
+/// - array: array (positional, required)
+///   The array to shuffle.
+///
+/// - returns: array
+///
+/// Display: Random Number Generator
+/// Category: calculate
+/// Returns: rng
+#[func]
+pub fn rng(
+    /// The seed of the generator.
+    #[external]
+    #[named]
+    seed: i64,
+) -> Value {
+    let Some(seed) = args.named::<i64>("seed")? else {
+        bail!(args.span, "missing argument: seed");
+    };
+    Value::dynamic(Rng::new(seed))
+}
+
+/// A seeded random number generator.
+///
+/// Uses the SplitMix64 algorithm, which is fast and has a small state that is
+/// cheap to copy. It is not suitable for cryptographic purposes.
+#[derive(Copy, Clone, PartialEq, Hash)]
+pub struct Rng {
+    /// The current state of the generator.
+    state: u64,
+}
+
+impl Rng {
+    /// Create a new generator from a seed.
+    pub fn new(seed: i64) -> Self {
+        Self { state: seed as u64 }
+    }
+
+    /// Call a method on a random number generator.
+    pub fn call_method(
+        mut self,
+        method: &str,
+        mut args: Args,
+        span: Span,
+    ) -> SourceResult<Value> {
+        let value = match method {
+            "int" => {
+                let start = args.expect("start")?;
+                let end = args.expect("end")?;
+                Value::Int(self.int(start, end).at(span)?)
+            }
+            "float" => Value::Float(self.float()),
+            "choice" => self.choice(&args.expect("array")?).at(span)?,
+            "shuffle" => Value::Array(self.shuffle(args.expect("array")?)),
+            _ => bail!(span, "type rng has no method `{}`", method),
+        };
+        args.finish()?;
+        Ok(Value::Array(array![value, Value::dynamic(self)]))
+    }
+
+    /// Generate a random integer in the range `start..end`.
+    pub fn int(&mut self, start: i64, end: i64) -> StrResult<i64> {
+        if end <= start {
+            return Err("end must be greater than start".into());
+        }
+
+        let len = end.wrapping_sub(start) as u64;
+        Ok(start.wrapping_add(self.below(len) as i64))
+    }
+
+    /// Generate a random float in the range `0.0..1.0`.
+    pub fn float(&mut self) -> f64 {
+        // Use the upper 53 bits, which fit exactly into the mantissa.
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Pick a random item from an array.
+    pub fn choice(&mut self, array: &Array) -> StrResult<Value> {
+        if array.as_slice().is_empty() {
+            return Err("cannot choose from an empty array".into());
+        }
+
+        let i = self.below(array.len() as u64);
+        Ok(array.at(i as i64)?.clone())
+    }
+
+    /// Randomly reorder the items of an array.
+    pub fn shuffle(&mut self, array: Array) -> Array {
+        let mut items: Vec<Value> = array.into_iter().collect();
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+        items.into_iter().collect()
+    }
+
+    /// Generate a random integer in the range `0..bound` without bias.
+    fn below(&mut self, bound: u64) -> u64 {
+        // Lemire's nearly divisionless method: Multiply into 128 bits and
+        // reject the few values that would skew the distribution.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = self.next() as u128 * bound as u128;
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Advance the state and return the next 64 random bits.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl Debug for Rng {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad("rng(..)")
+    }
+}
+
+cast_from_value! {
+    Rng: "rng",
+}
+
+/// Ensure that an integer argument is non-negative.
+fn non_negative(number: Spanned<i64>) -> SourceResult<u64> {
+    u64::try_from(number.v)
+        .map_err(|_| "number must not be negative")
+        .at(number.span)
+}
+
+/// Calculate the product of all integers from `start` to `end` (inclusive),
+/// returning `None` on overflow.
+fn product_range(start: u64, end: u64) -> Option<i64> {
+    (start.max(1)..=end).try_fold(1i64, |acc, n| acc.checked_mul(i64::try_from(n).ok()?))
+}
+
+/// Calculate a binomial coefficient, returning `None` on overflow.
+fn binomial(n: u64, k: u64) -> Option<i64> {
+    if k > n {
+        return Some(0);
+    }
+
+    // Use the smaller of the two symmetric coefficients and build the result
+    // incrementally so that every intermediate value is itself a binomial
+    // coefficient.
+    let k = k.min(n - k);
+    let mut result: u64 = 1;
+    for i in 0..k {
+        let factor = n - i;
+        let divisor = i + 1;
+        let g = gcd_u64(result, divisor);
+        result = (result / g).checked_mul(factor / (divisor / g))?;
+    }
+
+    i64::try_from(result).ok()
+}
+
+/// Calculate the greatest common divisor of two unsigned integers.
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let t = b;
+        b = a % b;
+        a = t;
+    }
+    a
+}
+
+/// The error message when a result does not fit into an integer.
+#[cold]
+fn too_large() -> &'static str {
+    "the result is too large"
+}
+
+/// Convert a float without fractional part into an integer, failing if it does
+/// not fit.
+fn float_to_int(n: f64) -> StrResult<i64> {
+    if n.is_nan() {
+        Err("the result is not a number".into())
+    } else if n < i64::MIN as f64 || n >= i64::MAX as f64 {
+        Err(too_large().into())
+    } else {
+        Ok(n as i64)
+    }
 }
 
 /// A value which can be passed to functions that work with integers and floats.
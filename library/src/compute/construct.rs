@@ -1,7 +1,7 @@
 use std::num::NonZeroI64;
 use std::str::FromStr;
 
-use typst::eval::Regex;
+use typst::eval::{Date, Datetime, Duration, Regex, Time};
 
 use crate::prelude::*;
 
@@ -316,6 +316,76 @@ cast_from_value! {
     v: f64 => Self(format_str!("{}", v)),
     v: Label => Self(v.0.into()),
     v: Str => Self(v),
+    v: Bytes => Self(v.to_str()?),
+}
+
+/// Convert a value to bytes.
+///
+/// - Strings are encoded in UTF-8.
+/// - Arrays of integers between `{0}` and `{255}` are converted directly.
+/// - Bytes are returned unchanged.
+///
+/// ## Example
+/// ```example
+/// #bytes("Hello 😃") \
+/// #bytes((123, 160, 22, 0)).len()
+/// ```
+///
+/// Display: Bytes
+/// Category: construct
+/// Returns: bytes
+#[func]
+pub fn bytes(
+    /// The value that should be converted to bytes.
+    value: ToBytes,
+) -> Value {
+    Value::Bytes(value.0)
+}
+
+/// A value that can be cast to bytes.
+struct ToBytes(Bytes);
+
+cast_from_value! {
+    ToBytes,
+    v: Str => Self(v.as_bytes().into()),
+    v: Array => Self(v.into_iter()
+        .map(|item| match item {
+            Value::Int(byte @ 0..=255) => Ok(byte as u8),
+            _ => Err("array must only contain integers between 0 and 255"),
+        })
+        .collect::<Result<Vec<u8>, _>>()?
+        .into()),
+    v: Bytes => Self(v),
+}
+
+/// Convert a value to an array.
+///
+/// Bytes are converted into an array of integers between `{0}` and `{255}`.
+///
+/// ## Example
+/// ```example
+/// #let hi = "👋"
+/// #array(bytes(hi))
+/// ```
+///
+/// Display: Array
+/// Category: construct
+/// Returns: array
+#[func]
+pub fn array(
+    /// The value that should be converted to an array.
+    value: ToArray,
+) -> Value {
+    Value::Array(value.0)
+}
+
+/// A value that can be cast to an array.
+struct ToArray(Array);
+
+cast_from_value! {
+    ToArray,
+    v: Array => Self(v),
+    v: Bytes => Self(v.to_array()),
 }
 
 /// Create a label from a string.
@@ -345,7 +415,9 @@ pub fn label(
     /// The name of the label.
     name: EcoString,
 ) -> Value {
-    Value::Label(Label(name))
+    let label = Label(name);
+    vm.vt.tracer.reference(label.clone());
+    Value::Label(label)
 }
 
 /// Create a regular expression from a string.
@@ -435,3 +507,184 @@ pub fn range(
 
     Value::Array(array)
 }
+
+/// Create a new datetime.
+///
+/// A datetime is either a date, a time or a combination of both. Which one
+/// you get depends on the components you specify: The year, month and day
+/// form a date and the hour, minute and second form a time. Each part must be
+/// given completely or not at all.
+///
+/// Datetimes can be compared, offset by a [duration]($func/duration) and
+/// subtracted from each other to get the duration between them.
+///
+/// ## Example
+/// ```example
+/// #let date = datetime(
+///   year: 2012,
+///   month: 8,
+///   day: 3,
+/// )
+///
+/// #date.display() \
+/// #date.display(
+///   "[day].[month].[year]"
+/// ) \
+/// #(date + duration(days: 30)).display()
+/// ```
+///
+/// ## Format
+/// _Note_: Depending on which components of the datetime you specify, Typst
+/// will store it in one of the following three ways:
+/// * If you specify year, month and day, Typst will store just a date.
+/// * If you specify hour, minute and second, Typst will store just a time.
+/// * If you specify all of year, month, day, hour, minute and second, Typst
+///   will store a full datetime.
+///
+/// Depending on how it is stored, the [`display`]($type/datetime.display)
+/// method will choose a different formatting by default.
+///
+/// ## Today
+/// The current date is available as `{datetime.today()}`. It takes an
+/// optional `offset` in hours from UTC. By default, the local date is used.
+///
+/// Display: Datetime
+/// Category: construct
+/// Returns: datetime
+#[func]
+#[scope(
+    scope.define("today", today);
+    scope
+)]
+pub fn datetime(
+    /// The year of the datetime.
+    #[named]
+    #[default]
+    year: Option<i64>,
+    /// The month of the datetime.
+    #[named]
+    #[default]
+    month: Option<i64>,
+    /// The day of the datetime.
+    #[named]
+    #[default]
+    day: Option<i64>,
+    /// The hour of the datetime.
+    #[named]
+    #[default]
+    hour: Option<i64>,
+    /// The minute of the datetime.
+    #[named]
+    #[default]
+    minute: Option<i64>,
+    /// The second of the datetime.
+    #[named]
+    #[default]
+    second: Option<i64>,
+) -> Value {
+    let date = match (year, month, day) {
+        (Some(year), Some(month), Some(day)) => {
+            Some(to_date(year, month, day).ok_or("date is invalid").at(args.span)?)
+        }
+        (None, None, None) => None,
+        _ => bail!(args.span, "date is incomplete"),
+    };
+
+    let time = match (hour, minute, second) {
+        (Some(hour), Some(minute), Some(second)) => {
+            Some(to_time(hour, minute, second).ok_or("time is invalid").at(args.span)?)
+        }
+        (None, None, None) => None,
+        _ => bail!(args.span, "time is incomplete"),
+    };
+
+    let datetime = match (date, time) {
+        (Some(date), Some(time)) => Datetime::Datetime(date, time),
+        (Some(date), None) => Datetime::Date(date),
+        (None, Some(time)) => Datetime::Time(time),
+        (None, None) => {
+            bail!(args.span, "at least one of date or time must be fully specified")
+        }
+    };
+
+    Value::Datetime(datetime)
+}
+
+/// Create a date from integer components, if they form a valid date.
+fn to_date(year: i64, month: i64, day: i64) -> Option<Date> {
+    Date::new(year.try_into().ok()?, month.try_into().ok()?, day.try_into().ok()?)
+}
+
+/// Create a time from integer components, if they form a valid time.
+fn to_time(hour: i64, minute: i64, second: i64) -> Option<Time> {
+    Time::new(hour.try_into().ok()?, minute.try_into().ok()?, second.try_into().ok()?)
+}
+
+/// Returns the current date.
+///
+/// ## Example
+/// ```example
+/// Today's date is
+/// #datetime.today().display().
+/// ```
+///
+/// Display: Today
+/// Category: construct
+/// Returns: datetime
+#[func]
+pub fn today(
+    /// An offset to apply to the current UTC date. If set to `{auto}`, the
+    /// offset will be the local offset.
+    #[named]
+    #[default]
+    offset: Smart<i64>,
+) -> Value {
+    let date = vm
+        .world()
+        .today(offset.as_custom())
+        .ok_or("unable to get the current date")
+        .at(args.span)?;
+    Value::Datetime(date)
+}
+
+/// Create a new duration.
+///
+/// All components are added up. A duration can be added to or subtracted from
+/// a [datetime]($func/datetime), multiplied with an integer and divided by
+/// another duration.
+///
+/// ## Example
+/// ```example
+/// #let d = duration(days: 1, hours: 6)
+/// #d.hours() \
+/// #repr(d * 2)
+/// ```
+///
+/// Display: Duration
+/// Category: construct
+/// Returns: duration
+#[func]
+pub fn duration(
+    /// The number of seconds.
+    #[named]
+    #[default]
+    seconds: i64,
+    /// The number of minutes.
+    #[named]
+    #[default]
+    minutes: i64,
+    /// The number of hours.
+    #[named]
+    #[default]
+    hours: i64,
+    /// The number of days.
+    #[named]
+    #[default]
+    days: i64,
+    /// The number of weeks.
+    #[named]
+    #[default]
+    weeks: i64,
+) -> Value {
+    Value::Duration(Duration::new(weeks, days, hours, minutes, seconds).at(args.span)?)
+}
@@ -4,9 +4,12 @@ use typst::diag::{format_xml_like_error, FileError};
 
 use crate::prelude::*;
 
-/// Read plain text from a file.
+/// Read plain text or data from a file.
 ///
-/// The file will be read and returned as a string.
+/// By default, the file will be read as UTF-8 and returned as a string.
+///
+/// If you specify `{encoding: none}`, this returns raw [bytes]($type/bytes)
+/// instead.
 ///
 /// ## Example
 /// ```example
@@ -18,19 +21,64 @@ use crate::prelude::*;
 ///
 /// Display: Plain text
 /// Category: data-loading
-/// Returns: string
+/// Returns: string or bytes
 #[func]
 pub fn read(
     /// Path to a file.
     path: Spanned<EcoString>,
+    /// The encoding to read the file with.
+    ///
+    /// If set to `{none}`, this function returns raw bytes.
+    #[named]
+    #[default(Some(Encoding::Utf8))]
+    encoding: Option<Encoding>,
 ) -> Value {
     let Spanned { v: path, span } = path;
     let path = vm.locate(&path).at(span)?;
     let data = vm.world().file(&path).at(span)?;
-    let text = std::str::from_utf8(&data)
-        .map_err(|_| "file is not valid utf-8")
-        .at(span)?;
-    Value::Str(text.into())
+    match encoding {
+        None => Value::Bytes(data.into()),
+        Some(Encoding::Utf8) => {
+            let text = std::str::from_utf8(&data)
+                .map_err(|_| "file is not valid utf-8")
+                .at(span)?;
+            Value::Str(text.into())
+        }
+    }
+}
+
+/// An encoding of a file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+enum Encoding {
+    /// The Unicode UTF-8 encoding.
+    Utf8,
+}
+
+/// A path to a file or raw bytes that should be loaded.
+enum Readable {
+    /// A path to a file.
+    Path(EcoString),
+    /// Raw bytes that were already loaded.
+    Bytes(Bytes),
+}
+
+impl Readable {
+    /// Load the data, reading it from a file if necessary.
+    fn load(self, vm: &Vm, span: Span) -> SourceResult<Bytes> {
+        match self {
+            Self::Path(path) => {
+                let path = vm.locate(&path).at(span)?;
+                Ok(vm.world().file(&path).at(span)?.into())
+            }
+            Self::Bytes(bytes) => Ok(bytes),
+        }
+    }
+}
+
+cast_from_value! {
+    Readable,
+    v: EcoString => Self::Path(v),
+    v: Bytes => Self::Bytes(v),
 }
 
 /// Read structured data from a CSV file.
@@ -56,8 +104,8 @@ pub fn read(
 /// Returns: array
 #[func]
 pub fn csv(
-    /// Path to a CSV file.
-    path: Spanned<EcoString>,
+    /// Path to a CSV file or raw CSV bytes.
+    path: Spanned<Readable>,
     /// The delimiter that separates columns in the CSV file.
     /// Must be a single ASCII character.
     /// Defaults to a comma.
@@ -66,8 +114,7 @@ pub fn csv(
     delimiter: Delimiter,
 ) -> Value {
     let Spanned { v: path, span } = path;
-    let path = vm.locate(&path).at(span)?;
-    let data = vm.world().file(&path).at(span)?;
+    let data = path.load(vm, span)?;
 
     let mut builder = csv::ReaderBuilder::new();
     builder.has_headers(false);
@@ -105,6 +152,10 @@ cast_from_value! {
     },
 }
 
+cast_to_value! {
+    v: Delimiter => Value::Str(char::from(v.0).into())
+}
+
 impl Default for Delimiter {
     fn default() -> Self {
         Self(b',')
@@ -171,12 +222,11 @@ fn format_csv_error(error: csv::Error) -> String {
 /// Returns: array or dictionary
 #[func]
 pub fn json(
-    /// Path to a JSON file.
-    path: Spanned<EcoString>,
+    /// Path to a JSON file or raw JSON bytes.
+    path: Spanned<Readable>,
 ) -> Value {
     let Spanned { v: path, span } = path;
-    let path = vm.locate(&path).at(span)?;
-    let data = vm.world().file(&path).at(span)?;
+    let data = path.load(vm, span)?;
     let value: serde_json::Value =
         serde_json::from_slice(&data).map_err(format_json_error).at(span)?;
     convert_json(value)
@@ -264,12 +314,11 @@ fn format_json_error(error: serde_json::Error) -> String {
 /// Returns: array
 #[func]
 pub fn xml(
-    /// Path to an XML file.
-    path: Spanned<EcoString>,
+    /// Path to an XML file or raw XML bytes.
+    path: Spanned<Readable>,
 ) -> Value {
     let Spanned { v: path, span } = path;
-    let path = vm.locate(&path).at(span)?;
-    let data = vm.world().file(&path).at(span)?;
+    let data = path.load(vm, span)?;
     let text = std::str::from_utf8(&data).map_err(FileError::from).at(span)?;
     let document = roxmltree::Document::parse(text).map_err(format_xml_error).at(span)?;
     convert_xml(document.root())
@@ -0,0 +1,15 @@
+//! System-related things.
+
+use typst::eval::{Module, Scope};
+
+use crate::prelude::*;
+
+/// A module with system-related things.
+///
+/// The `inputs` are the key-value pairs that were passed to the compiler from
+/// the outside, e.g. through the command line.
+pub fn module(inputs: Dict) -> Module {
+    let mut scope = Scope::new();
+    scope.define("inputs", Value::Dict(inputs));
+    Module::new("sys").with_scope(scope)
+}
@@ -51,6 +51,7 @@ use typed_arena::Arena;
 use typst::diag::SourceResult;
 use typst::eval::Tracer;
 use typst::model::{applicable, realize, StyleVecBuilder};
+use typst::util::timing;
 
 use crate::math::{EquationElem, LayoutMath};
 use crate::meta::DocumentElem;
@@ -140,6 +141,8 @@ impl Layout for Content {
             styles: StyleChain,
             regions: Regions,
         ) -> SourceResult<Fragment> {
+            timing::memo_miss("layout");
+            let _scope = timing::scope("layout", || content.func().name().into());
             let mut vt = Vt { world, tracer, provider, introspector };
             let scratch = Scratch::default();
             let (realized, styles) = realize_block(&mut vt, &scratch, content, styles)?;
@@ -149,6 +152,7 @@ impl Layout for Content {
                 .layout(&mut vt, styles, regions)
         }
 
+        timing::memo_call("layout");
         cached(
             self,
             vt.world,
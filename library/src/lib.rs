@@ -11,7 +11,7 @@ pub mod text;
 pub mod visualize;
 
 use typst::diag::At;
-use typst::eval::{LangItems, Library, Module, Scope};
+use typst::eval::{Dict, LangItems, Library, Module, Scope};
 use typst::geom::{Align, Color, Dir, GenAlign, Smart};
 use typst::model::{Element, Styles};
 
@@ -19,14 +19,21 @@ use self::layout::LayoutRoot;
 
 /// Construct the standard library.
 pub fn build() -> Library {
+    build_with_inputs(Dict::new())
+}
+
+/// Construct the standard library with inputs that are accessible to documents
+/// as `sys.inputs`.
+pub fn build_with_inputs(inputs: Dict) -> Library {
     let math = math::module();
     let calc = compute::calc::module();
-    let global = global(math.clone(), calc);
+    let sys = compute::sys::module(inputs);
+    let global = global(math.clone(), calc, sys);
     Library { global, math, styles: styles(), items: items() }
 }
 
 /// Construct the module with global definitions.
-fn global(math: Module, calc: Module) -> Module {
+fn global(math: Module, calc: Module, sys: Module) -> Module {
     let mut global = Scope::deduplicating();
 
     // Text.
@@ -117,9 +124,13 @@ fn global(math: Module, calc: Module) -> Module {
     global.define("cmyk", compute::cmyk);
     global.define("symbol", compute::symbol);
     global.define("str", compute::str);
+    global.define("bytes", compute::bytes);
+    global.define("array", compute::array);
     global.define("label", compute::label);
     global.define("regex", compute::regex);
     global.define("range", compute::range);
+    global.define("datetime", compute::datetime);
+    global.define("duration", compute::duration);
     global.define("read", compute::read);
     global.define("csv", compute::csv);
     global.define("json", compute::json);
@@ -128,6 +139,9 @@ fn global(math: Module, calc: Module) -> Module {
     // Calc.
     global.define("calc", calc);
 
+    // System.
+    global.define("sys", sys);
+
     // Colors.
     global.define("black", Color::BLACK);
     global.define("gray", Color::GRAY);
@@ -236,6 +250,8 @@ fn items() -> LangItems {
                 counter.call_method(vm, method, args, span)
             } else if let Some(state) = dynamic.downcast::<meta::State>().cloned() {
                 state.call_method(vm, method, args, span)
+            } else if let Some(&rng) = dynamic.downcast::<compute::calc::Rng>() {
+                rng.call_method(method, args, span)
             } else {
                 Err(format!("type {} has no method `{method}`", dynamic.type_name()))
                     .at(span)
@@ -8,7 +8,7 @@ use hayagriva::io::{BibLaTeXError, YamlBibliographyError};
 use hayagriva::style::{self, Brackets, Citation, Database, DisplayString, Formatting};
 use hayagriva::Entry;
 
-use super::{LocalName, RefElem};
+use super::{DocumentElem, LocalName, RefElem};
 use crate::layout::{BlockElem, GridElem, ParElem, Sizing, TrackSizings, VElem};
 use crate::meta::HeadingElem;
 use crate::prelude::*;
@@ -302,20 +302,28 @@ impl Synthesize for CiteElem {
 }
 
 impl Show for CiteElem {
-    fn show(&self, vt: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
         if !vt.introspector.init() {
             return Ok(Content::empty());
         }
 
         let works = Works::new(vt).at(self.span())?;
         let location = self.0.location().unwrap();
-        works
-            .citations
-            .get(&location)
-            .cloned()
-            .flatten()
-            .ok_or("bibliography does not contain this key")
-            .at(self.span())
+        if let Some(citation) = works.citations.get(&location).cloned().flatten() {
+            return Ok(citation);
+        }
+
+        if DocumentElem::draft_in(styles) {
+            let keys = self.keys().join(", ");
+            vt.tracer.warn(warning!(
+                self.span(),
+                "bibliography does not contain the key {}",
+                keys,
+            ));
+            return Ok(TextElem::packed("??"));
+        }
+
+        bail!(self.span(), "bibliography does not contain this key")
     }
 }
 
@@ -1,3 +1,5 @@
+use typst::util::hash128;
+
 use crate::prelude::*;
 
 /// Provides access to the location of content.
@@ -40,7 +42,9 @@ impl Show for LocateElem {
         }
 
         let location = self.0.location().unwrap();
-        Ok(self.func().call_vt(vt, [location.into()])?.display())
+        let content = self.func().call_vt(vt, [location.into()])?.display();
+        vt.tracer.introspect(location, self.span(), hash128(&content));
+        Ok(content)
     }
 }
 
@@ -4,6 +4,7 @@ use std::str::FromStr;
 use ecow::{eco_vec, EcoVec};
 use smallvec::{smallvec, SmallVec};
 use typst::eval::Tracer;
+use typst::util::hash128;
 
 use super::{FigureElem, HeadingElem, Numbering, NumberingPattern};
 use crate::layout::PageElem;
@@ -280,9 +281,10 @@ impl Counter {
         span: Span,
     ) -> SourceResult<Value> {
         let value = match method {
-            "display" => {
-                self.display(args.eat()?, args.named("both")?.unwrap_or(false)).into()
-            }
+            "display" => self
+                .display(args.eat()?, args.named("both")?.unwrap_or(false))
+                .spanned(span)
+                .into(),
             "step" => self
                 .update(CounterUpdate::Step(
                     args.named("level")?.unwrap_or(NonZeroUsize::ONE),
@@ -609,7 +611,10 @@ impl Show for DisplayElem {
         } else {
             counter.at(vt, location)?
         };
-        state.display(vt, &numbering)
+
+        let content = state.display(vt, &numbering)?;
+        vt.tracer.introspect(location, self.span(), hash128(&content));
+        Ok(content)
     }
 }
 
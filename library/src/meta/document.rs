@@ -21,6 +21,18 @@ pub struct DocumentElem {
     /// The document's authors.
     pub author: Author,
 
+    /// Whether the document is a draft.
+    ///
+    /// In draft mode, references to labels and bibliography keys that do not
+    /// exist produce a warning and a `??` placeholder instead of an error.
+    /// This way, the document can be compiled while it is still incomplete.
+    ///
+    /// ```example
+    /// #set document(draft: true)
+    /// As shown in @missing, ...
+    /// ```
+    pub draft: bool,
+
     /// The page runs.
     #[internal]
     #[variadic]
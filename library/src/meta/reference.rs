@@ -1,4 +1,4 @@
-use super::{BibliographyElem, CiteElem, Counter, LocalName, Numbering};
+use super::{BibliographyElem, CiteElem, Counter, DocumentElem, LocalName, Numbering};
 use crate::prelude::*;
 use crate::text::TextElem;
 
@@ -104,6 +104,15 @@ impl Show for RefElem {
         }
 
         let [elem] = matches.as_slice() else {
+            if matches.is_empty() && DocumentElem::draft_in(styles) {
+                vt.tracer.warn(warning!(
+                    self.span(),
+                    "label `{:?}` does not exist in the document",
+                    target,
+                ));
+                return Ok(TextElem::packed("??"));
+            }
+
             bail!(self.span(), if matches.is_empty() {
                 "label does not exist in the document"
             } else {
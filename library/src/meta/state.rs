@@ -2,6 +2,7 @@ use std::fmt::{self, Debug, Formatter, Write};
 
 use ecow::{eco_vec, EcoVec};
 use typst::eval::Tracer;
+use typst::util::hash128;
 
 use crate::prelude::*;
 
@@ -264,7 +265,7 @@ impl State {
         span: Span,
     ) -> SourceResult<Value> {
         let value = match method {
-            "display" => self.display(args.eat()?).into(),
+            "display" => self.display(args.eat()?).spanned(span).into(),
             "at" => self.at(&mut vm.vt, args.expect("location")?)?,
             "final" => self.final_(&mut vm.vt, args.expect("location")?)?,
             "update" => self.update(args.expect("value or function")?).into(),
@@ -395,10 +396,12 @@ impl Show for DisplayElem {
     fn show(&self, vt: &mut Vt, _: StyleChain) -> SourceResult<Content> {
         let location = self.0.location().unwrap();
         let value = self.state().at(vt, location)?;
-        Ok(match self.func() {
+        let content = match self.func() {
             Some(func) => func.call_vt(vt, [value])?.display(),
             None => value.display(),
-        })
+        };
+        vt.tracer.introspect(location, self.span(), hash128(&content));
+        Ok(content)
     }
 }
 
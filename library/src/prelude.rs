@@ -10,13 +10,13 @@ pub use comemo::{Track, Tracked, TrackedMut};
 #[doc(no_inline)]
 pub use ecow::{eco_format, EcoString};
 #[doc(no_inline)]
-pub use typst::diag::{bail, error, At, SourceResult, StrResult};
+pub use typst::diag::{bail, error, warning, At, SourceResult, StrResult};
 #[doc(no_inline)]
 pub use typst::doc::*;
 #[doc(no_inline)]
 pub use typst::eval::{
-    array, cast_from_value, cast_to_value, dict, format_str, func, Args, Array, Cast,
-    CastInfo, Dict, Func, Never, Str, Symbol, Value, Vm,
+    array, cast_from_value, cast_to_value, dict, format_str, func, Args, Array, Bytes,
+    Cast, CastInfo, Dict, Func, Never, Str, Symbol, Value, Vm,
 };
 #[doc(no_inline)]
 pub use typst::geom::*;
@@ -59,6 +59,13 @@ pub struct TextElem {
     /// هذا عربي.
     ///
     /// ```
+    #[parse(
+        let font_list: Option<Spanned<FontList>> = args.named("font")?;
+        if let Some(font_list) = &font_list {
+            warn_unknown_families(vm, font_list);
+        }
+        font_list.map(|font_list| font_list.v)
+    )]
     #[default(FontList(vec![FontFamily::new("Linux Libertine")]))]
     pub font: FontList,
 
@@ -487,16 +494,38 @@ impl TextElem {
 }
 
 impl Construct for TextElem {
-    fn construct(_: &mut Vm, args: &mut Args) -> SourceResult<Content> {
+    fn construct(vm: &mut Vm, args: &mut Args) -> SourceResult<Content> {
         // The text constructor is special: It doesn't create a text element.
         // Instead, it leaves the passed argument structurally unchanged, but
         // styles all text in it.
-        let styles = Self::set(args)?;
+        let styles = Self::set(vm, args)?;
         let body = args.expect::<Content>("body")?;
         Ok(body.styled_with_map(styles))
     }
 }
 
+/// Warn about the font families in a font list if none of them is available.
+///
+/// Since text is shaped with fallback fonts anyway, there is nothing to warn
+/// about as long as at least one of the families is known.
+fn warn_unknown_families(vm: &mut Vm, font_list: &Spanned<FontList>) {
+    let world = vm.world();
+    let book = world.book();
+    let families = &font_list.v.0;
+    if families.iter().any(|family| book.contains_family(family.as_str())) {
+        return;
+    }
+
+    let names: Vec<_> = families.iter().map(FontFamily::as_str).collect();
+    let message = match names.as_slice() {
+        [] => return,
+        [name] => eco_format!("unknown font family: {}", name),
+        _ => eco_format!("unknown font families: {}", names.join(", ")),
+    };
+
+    vm.vt.tracer.warn(warning!(font_list.span, message));
+}
+
 /// A lowercased font family like "arial".
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct FontFamily(EcoString);
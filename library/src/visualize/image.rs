@@ -24,16 +24,23 @@ use crate::prelude::*;
 /// Category: visualize
 #[element(Layout)]
 pub struct ImageElem {
-    /// Path to an image file.
+    /// Path to an image file or raw image bytes.
+    ///
+    /// When passing bytes, the format is detected from the data.
     #[required]
     #[parse(
         let Spanned { v: path, span } =
-            args.expect::<Spanned<EcoString>>("path to image file")?;
-        let path: EcoString = vm.locate(&path).at(span)?.to_string_lossy().into();
+            args.expect::<Spanned<ImageSource>>("path to image file")?;
+        let path = match path {
+            ImageSource::Path(path) => ImageSource::Path(
+                vm.locate(&path).at(span)?.to_string_lossy().into(),
+            ),
+            ImageSource::Bytes(bytes) => ImageSource::Bytes(bytes),
+        };
         let _ = load(vm.world(), &path).at(span)?;
         path
     )]
-    pub path: EcoString,
+    pub path: ImageSource,
 
     /// The width of the image.
     pub width: Smart<Rel<Length>>,
@@ -124,18 +131,67 @@ pub enum ImageFit {
     Stretch,
 }
 
-/// Load an image from a path.
+/// Where to load an image from.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum ImageSource {
+    /// A path to an image file.
+    Path(EcoString),
+    /// Raw image data.
+    Bytes(Bytes),
+}
+
+cast_from_value! {
+    ImageSource,
+    v: EcoString => Self::Path(v),
+    v: Bytes => Self::Bytes(v),
+}
+
+cast_to_value! {
+    v: ImageSource => match v {
+        ImageSource::Path(path) => path.into(),
+        ImageSource::Bytes(bytes) => Value::Bytes(bytes),
+    }
+}
+
+/// Load an image from a path or from raw data.
 #[comemo::memoize]
-fn load(world: Tracked<dyn World>, full: &str) -> StrResult<Image> {
-    let full = Path::new(full);
-    let buffer = world.file(full)?;
-    let ext = full.extension().and_then(OsStr::to_str).unwrap_or_default();
-    let format = match ext.to_lowercase().as_str() {
-        "png" => ImageFormat::Raster(RasterFormat::Png),
-        "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
-        "gif" => ImageFormat::Raster(RasterFormat::Gif),
-        "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
-        _ => return Err("unknown image format".into()),
-    };
-    Image::new(buffer, format)
+fn load(world: Tracked<dyn World>, source: &ImageSource) -> StrResult<Image> {
+    match source {
+        ImageSource::Path(full) => {
+            let full = Path::new(full.as_str());
+            let buffer = world.file(full)?;
+            let ext = full.extension().and_then(OsStr::to_str).unwrap_or_default();
+            let format = match ext.to_lowercase().as_str() {
+                "png" => ImageFormat::Raster(RasterFormat::Png),
+                "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
+                "gif" => ImageFormat::Raster(RasterFormat::Gif),
+                "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
+                _ => return Err("unknown image format".into()),
+            };
+            Image::new(buffer, format)
+        }
+        ImageSource::Bytes(bytes) => {
+            let format = detect_format(bytes).ok_or("unknown image format")?;
+            Image::new(bytes.buffer().clone(), format)
+        }
+    }
+}
+
+/// Detect the format of raw image data from its leading bytes.
+fn detect_format(data: &[u8]) -> Option<ImageFormat> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(ImageFormat::Raster(RasterFormat::Png))
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(ImageFormat::Raster(RasterFormat::Jpg))
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some(ImageFormat::Raster(RasterFormat::Gif))
+    } else if data.starts_with(&[0x1F, 0x8B]) {
+        // Gzip-compressed SVG.
+        Some(ImageFormat::Vector(VectorFormat::Svg))
+    } else {
+        let head = &data[..data.len().min(1024)];
+        let text = String::from_utf8_lossy(head);
+        text.contains("<svg")
+            .then_some(ImageFormat::Vector(VectorFormat::Svg))
+    }
 }
@@ -28,7 +28,7 @@ struct Field {
     synthesized: bool,
     fold: bool,
     resolve: bool,
-    parse: Option<FieldParser>,
+    parse: Option<BlockWithReturn>,
     default: syn::Expr,
     vis: syn::Visibility,
     ident: Ident,
@@ -50,21 +50,6 @@ impl Field {
     }
 }
 
-struct FieldParser {
-    prefix: Vec<syn::Stmt>,
-    expr: syn::Stmt,
-}
-
-impl Parse for FieldParser {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let mut stmts = syn::Block::parse_within(input)?;
-        let Some(expr) = stmts.pop() else {
-            return Err(input.error("expected at least on expression"));
-        };
-        Ok(Self { prefix: stmts, expr })
-    }
-}
-
 /// Preprocess the element's definition.
 fn prepare(stream: TokenStream, body: &syn::ItemStruct) -> Result<Elem> {
     let syn::Fields::Named(named) = &body.fields else {
@@ -372,6 +357,7 @@ fn create_pack_impl(element: &Elem) -> TokenStream {
                         params: ::std::vec![#(#infos),*],
                         returns: ::std::vec!["content"],
                         category: #category,
+                        scope: ::typst::eval::Scope::new(),
                     }),
                 };
                 (&NATIVE).into()
@@ -407,9 +393,23 @@ fn create_vtable_func(element: &Elem) -> TokenStream {
 
 /// Create a parameter info for a field.
 fn create_param_info(field: &Field) -> TokenStream {
-    let Field { name, docs, positional, variadic, required, ty, .. } = field;
+    let Field {
+        name,
+        docs,
+        positional,
+        variadic,
+        required,
+        ty,
+        default,
+        ..
+    } = field;
     let named = !positional;
     let settable = field.settable();
+    let default = if settable && !field.external && !field.fold {
+        quote! { Some(|| { let value: #ty = #default; value.into() }) }
+    } else {
+        quote! { None }
+    };
     let ty = if *variadic {
         quote! { <#ty as ::typst::eval::Variadics>::Inner }
     } else {
@@ -427,6 +427,7 @@ fn create_param_info(field: &Field) -> TokenStream {
             variadic: #variadic,
             required: #required,
             settable: #settable,
+            default: #default,
         }
     }
 }
@@ -502,6 +503,7 @@ fn create_set_impl(element: &Elem) -> TokenStream {
     quote! {
         impl ::typst::model::Set for #ident {
             fn set(
+                vm: &mut ::typst::eval::Vm,
                 args: &mut ::typst::eval::Args,
             ) -> ::typst::diag::SourceResult<::typst::model::Styles> {
                 let mut styles = ::typst::model::Styles::new();
@@ -514,7 +516,7 @@ fn create_set_impl(element: &Elem) -> TokenStream {
 
 /// Create argument parsing code for a field.
 fn create_field_parser(field: &Field) -> (TokenStream, TokenStream) {
-    if let Some(FieldParser { prefix, expr }) = &field.parse {
+    if let Some(BlockWithReturn { prefix, expr }) = &field.parse {
         return (quote! { #(#prefix);* }, quote! { #expr });
     }
 
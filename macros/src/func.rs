@@ -17,6 +17,7 @@ struct Func {
     ident: Ident,
     params: Vec<Param>,
     returns: Vec<String>,
+    scope: Option<BlockWithReturn>,
     body: syn::Block,
 }
 
@@ -72,7 +73,8 @@ fn prepare(item: &syn::ItemFn) -> Result<Func> {
         validate_attrs(&attrs)?;
     }
 
-    let docs = documentation(&item.attrs);
+    let mut attrs = item.attrs.clone();
+    let docs = documentation(&attrs);
     let mut lines = docs.split("\n").collect();
     let returns = meta_line(&mut lines, "Returns")?
         .split(" or ")
@@ -91,10 +93,11 @@ fn prepare(item: &syn::ItemFn) -> Result<Func> {
         ident: sig.ident.clone(),
         params,
         returns,
+        scope: parse_attr(&mut attrs, "scope")?.flatten(),
         body: (*item.block).clone(),
     };
 
-    validate_attrs(&item.attrs)?;
+    validate_attrs(&attrs)?;
     Ok(func)
 }
 
@@ -108,11 +111,13 @@ fn create(func: &Func) -> TokenStream {
         ident,
         params,
         returns,
+        scope,
         body,
         ..
     } = func;
     let handlers = params.iter().filter(|param| !param.external).map(create_param_parser);
     let params = params.iter().map(create_param_info);
+    let scope = create_scope_builder(scope.as_ref());
     quote! {
         #[doc = #docs]
         #vis fn #ident() -> &'static ::typst::eval::NativeFunc {
@@ -129,6 +134,7 @@ fn create(func: &Func) -> TokenStream {
                     params: ::std::vec![#(#params),*],
                     returns: ::std::vec![#(#returns),*],
                     category: #category,
+                    scope: #scope,
                 }),
             };
             &FUNC
@@ -136,11 +142,30 @@ fn create(func: &Func) -> TokenStream {
     }
 }
 
+/// Create a block that evaluates to the function's scope.
+fn create_scope_builder(scope: Option<&BlockWithReturn>) -> TokenStream {
+    if let Some(BlockWithReturn { prefix, expr }) = scope {
+        quote! { {
+            let mut scope = ::typst::eval::Scope::deduplicating();
+            #(#prefix);*
+            #expr
+        } }
+    } else {
+        quote! { ::typst::eval::Scope::new() }
+    }
+}
+
 /// Create a parameter info for a field.
 fn create_param_info(param: &Param) -> TokenStream {
     let Param { name, docs, named, variadic, ty, default, .. } = param;
     let positional = !named;
     let required = default.is_none();
+    let default = match default {
+        Some(default) if !param.external => {
+            quote! { Some(|| { let value: #ty = #default; value.into() }) }
+        }
+        _ => quote! { None },
+    };
     let ty = if *variadic {
         quote! { <#ty as ::typst::eval::Variadics>::Inner }
     } else {
@@ -158,6 +183,7 @@ fn create_param_info(param: &Param) -> TokenStream {
             variadic: #variadic,
             required: #required,
             settable: false,
+            default: #default,
         }
     }
 }
@@ -88,3 +88,19 @@ pub fn meta_line<'a>(lines: &mut Vec<&'a str>, key: &str) -> Result<&'a str> {
         None => bail!(callsite, "missing metadata key: {}", key),
     }
 }
+
+/// A block whose last statement is the returned expression.
+pub struct BlockWithReturn {
+    pub prefix: Vec<syn::Stmt>,
+    pub expr: syn::Stmt,
+}
+
+impl Parse for BlockWithReturn {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut stmts = syn::Block::parse_within(input)?;
+        let Some(expr) = stmts.pop() else {
+            return Err(input.error("expected at least on expression"));
+        };
+        Ok(Self { prefix: stmts, expr })
+    }
+}
@@ -44,13 +44,30 @@ macro_rules! __error {
 
 #[doc(inline)]
 pub use crate::__error as error;
+
+/// Construct a [`SourceError`] with [warning](Severity::Warning) severity.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __warning {
+    ($span:expr, $message:expr $(,)?) => {
+        $crate::diag::SourceError::new($span, $message)
+            .with_severity($crate::diag::Severity::Warning)
+    };
+
+    ($span:expr, $fmt:expr, $($arg:expr),+ $(,)?) => {
+        $crate::diag::warning!($span, $crate::diag::eco_format!($fmt, $($arg),+))
+    };
+}
+
+#[doc(inline)]
+pub use crate::__warning as warning;
 #[doc(hidden)]
 pub use ecow::eco_format;
 
 /// A result that can carry multiple source errors.
 pub type SourceResult<T> = Result<T, Box<Vec<SourceError>>>;
 
-/// An error in a source file.
+/// An error or warning in a source file.
 ///
 /// The contained spans will only be detached if any of the input source files
 /// were detached.
@@ -60,6 +77,8 @@ pub struct SourceError {
     pub span: Span,
     /// The position in the node where the error should be annotated.
     pub pos: ErrorPos,
+    /// Whether this is a fatal error or just a warning.
+    pub severity: Severity,
     /// A diagnostic message describing the problem.
     pub message: EcoString,
     /// The trace of function calls leading to the error.
@@ -73,6 +92,7 @@ impl SourceError {
         Self {
             span,
             pos: ErrorPos::Full,
+            severity: Severity::Error,
             trace: vec![],
             message: message.into(),
         }
@@ -84,6 +104,12 @@ impl SourceError {
         self
     }
 
+    /// Adjust the severity of the diagnostic.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
     /// The range in the source file identified by
     /// [`self.span.source()`](Span::source) where the error should be
     /// annotated.
@@ -97,6 +123,16 @@ impl SourceError {
     }
 }
 
+/// How severe a [`SourceError`] is.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Severity {
+    /// A fatal error that prevents compilation from finishing.
+    Error,
+    /// A problem that does not prevent compilation, but likely indicates a
+    /// mistake in the document.
+    Warning,
+}
+
 /// A part of an error's [trace](SourceError::trace).
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Tracepoint {
@@ -193,6 +229,8 @@ pub enum FileError {
     NotSource,
     /// The file was not valid UTF-8, but should have been.
     InvalidUtf8,
+    /// A package with the given specification was not found.
+    PackageNotFound(EcoString),
     /// Another error.
     Other,
 }
@@ -225,6 +263,7 @@ impl Display for FileError {
             Self::IsDirectory => f.pad("failed to load file (is a directory)"),
             Self::NotSource => f.pad("not a typst source file"),
             Self::InvalidUtf8 => f.pad("file is not valid utf-8"),
+            Self::PackageNotFound(spec) => write!(f, "package not found ({spec})"),
             Self::Other => f.pad("failed to load file"),
         }
     }
@@ -1,11 +1,13 @@
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
+use std::num::NonZeroUsize;
 use std::ops::{Add, AddAssign};
 
 use ecow::{eco_format, EcoString, EcoVec};
 
 use super::{ops, Args, Func, Value, Vm};
 use crate::diag::{At, SourceResult, StrResult};
+use crate::syntax::Span;
 use crate::util::pretty_array_like;
 
 /// Create a new [`Array`] from values.
@@ -271,13 +273,29 @@ impl Array {
         Ok(result)
     }
 
-    /// Return a sorted version of this array.
+    /// Return a sorted version of this array, optionally by a key function.
     ///
     /// Returns an error if two values could not be compared.
-    pub fn sorted(&self) -> StrResult<Self> {
+    pub fn sorted(
+        &self,
+        vm: &mut Vm,
+        span: Span,
+        key: Option<Func>,
+    ) -> SourceResult<Self> {
+        let mut pairs = Vec::with_capacity(self.0.len());
+        for item in self.iter() {
+            let key = match &key {
+                Some(func) => {
+                    let args = Args::new(func.span(), [item.clone()]);
+                    func.call_vm(vm, args)?
+                }
+                None => item.clone(),
+            };
+            pairs.push((key, item.clone()));
+        }
+
         let mut result = Ok(());
-        let mut vec = self.0.clone();
-        vec.make_mut().sort_by(|a, b| {
+        pairs.sort_by(|(a, _), (b, _)| {
             a.partial_cmp(b).unwrap_or_else(|| {
                 if result.is_ok() {
                     result = Err(eco_format!(
@@ -289,7 +307,99 @@ impl Array {
                 Ordering::Equal
             })
         });
-        result.map(|_| Self::from_vec(vec))
+
+        result.at(span)?;
+        Ok(pairs.into_iter().map(|(_, item)| item).collect())
+    }
+
+    /// Combine this array with another one into an array of pairs.
+    ///
+    /// The result is as long as the shorter of the two arrays.
+    pub fn zip(&self, other: &Array) -> Self {
+        self.iter()
+            .zip(other)
+            .map(|(a, b)| Value::Array(array![a.clone(), b.clone()]))
+            .collect()
+    }
+
+    /// Pair each item with its index.
+    pub fn enumerate(&self) -> Self {
+        self.iter()
+            .enumerate()
+            .map(|(i, item)| Value::Array(array![Value::Int(i as i64), item.clone()]))
+            .collect()
+    }
+
+    /// Add all items of the array together.
+    ///
+    /// Returns the default if the array is empty and fails if there is none.
+    pub fn sum(&self, default: Option<Value>) -> StrResult<Value> {
+        let mut iter = self.iter().cloned();
+        let mut acc = iter
+            .next()
+            .or(default)
+            .ok_or("cannot calculate sum of empty array with no default")?;
+        for item in iter {
+            acc = ops::add(acc, item)?;
+        }
+        Ok(acc)
+    }
+
+    /// Multiply all items of the array together.
+    ///
+    /// Returns the default if the array is empty and fails if there is none.
+    pub fn product(&self, default: Option<Value>) -> StrResult<Value> {
+        let mut iter = self.iter().cloned();
+        let mut acc = iter
+            .next()
+            .or(default)
+            .ok_or("cannot calculate product of empty array with no default")?;
+        for item in iter {
+            acc = ops::mul(acc, item)?;
+        }
+        Ok(acc)
+    }
+
+    /// Return a new array without duplicate items, keeping the first
+    /// occurrence of each item.
+    pub fn dedup(&self) -> Self {
+        let mut unique = EcoVec::with_capacity(self.0.len());
+        for item in self.iter() {
+            if !unique.contains(item) {
+                unique.push(item.clone());
+            }
+        }
+        Self::from_vec(unique)
+    }
+
+    /// Split the array into chunks of the given size. The last chunk may be
+    /// shorter.
+    pub fn chunks(&self, size: NonZeroUsize) -> Self {
+        self.0
+            .chunks(size.get())
+            .map(|chunk| Value::Array(chunk.iter().cloned().collect()))
+            .collect()
+    }
+
+    /// Return all overlapping windows of the given size.
+    pub fn windows(&self, size: NonZeroUsize) -> Self {
+        self.0
+            .windows(size.get())
+            .map(|window| Value::Array(window.iter().cloned().collect()))
+            .collect()
+    }
+
+    /// Return a new array with a separator placed between each pair of
+    /// adjacent items.
+    pub fn intersperse(&self, sep: Value) -> Self {
+        let mut result = EcoVec::with_capacity((2 * self.0.len()).saturating_sub(1));
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                result.push(sep.clone());
+            }
+            result.push(item.clone());
+        }
+        Self::from_vec(result)
     }
 
     /// Repeat this array `n` times.
@@ -0,0 +1,137 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Add, AddAssign, Deref};
+
+use ecow::{eco_format, EcoString};
+
+use super::{Array, Str, Value};
+use crate::diag::StrResult;
+use crate::util::Buffer;
+
+/// A sequence of bytes.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct Bytes(Buffer);
+
+impl Bytes {
+    /// The length of the byte sequence.
+    pub fn len(&self) -> i64 {
+        self.0.len() as i64
+    }
+
+    /// Whether the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The underlying buffer.
+    pub fn buffer(&self) -> &Buffer {
+        &self.0
+    }
+
+    /// Return a view into the bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Extract the byte at the given index.
+    pub fn at(&self, index: i64) -> StrResult<u8> {
+        self.locate(index)
+            .and_then(|i| self.0.get(i).copied())
+            .ok_or_else(|| out_of_bounds(index, self.len()))
+    }
+
+    /// Extract a contiguous subregion of the bytes.
+    pub fn slice(&self, start: i64, end: Option<i64>) -> StrResult<Self> {
+        let len = self.len();
+        let start = self
+            .locate(start)
+            .filter(|&start| start <= self.0.len())
+            .ok_or_else(|| out_of_bounds(start, len))?;
+
+        let end = end.unwrap_or(self.len());
+        let end = self
+            .locate(end)
+            .filter(|&end| end <= self.0.len())
+            .ok_or_else(|| out_of_bounds(end, len))?
+            .max(start);
+
+        Ok(Self(self.0[start..end].into()))
+    }
+
+    /// Decode the bytes as a UTF-8 string.
+    pub fn to_str(&self) -> StrResult<Str> {
+        std::str::from_utf8(&self.0)
+            .map(Into::into)
+            .map_err(|_| "bytes are not valid utf-8".into())
+    }
+
+    /// Convert the bytes into an array of integers.
+    pub fn to_array(&self) -> Array {
+        self.0.iter().map(|&byte| Value::Int(byte.into())).collect()
+    }
+
+    /// Resolve an index.
+    fn locate(&self, index: i64) -> Option<usize> {
+        usize::try_from(if index >= 0 { index } else { self.len().checked_add(index)? })
+            .ok()
+    }
+}
+
+impl From<Buffer> for Bytes {
+    fn from(buffer: Buffer) -> Self {
+        Self(buffer)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(slice: &[u8]) -> Self {
+        Self(slice.into())
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(vec: Vec<u8>) -> Self {
+        Self(vec.into())
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Debug for Bytes {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "bytes({})", self.len())
+    }
+}
+
+impl Add for Bytes {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for Bytes {
+    fn add_assign(&mut self, rhs: Self) {
+        if rhs.is_empty() {
+            // Nothing to do
+        } else if self.is_empty() {
+            *self = rhs;
+        } else {
+            let mut vec = self.0.to_vec();
+            vec.extend_from_slice(&rhs.0);
+            *self = vec.into();
+        }
+    }
+}
+
+/// The out of bounds access error message.
+fn out_of_bounds(index: i64, len: i64) -> EcoString {
+    eco_format!("byte index out of bounds (index: {}, len: {})", index, len)
+}
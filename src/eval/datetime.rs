@@ -0,0 +1,487 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
+
+use ecow::{eco_format, EcoString};
+
+use super::Duration;
+use crate::diag::StrResult;
+
+/// The number of seconds in a day.
+const DAY: i64 = 24 * 60 * 60;
+
+/// The English names of the months.
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The English names of the weekdays, starting on Monday.
+const WEEKDAYS: [&str; 7] =
+    ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+/// A date, a time, or a combination of both.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Datetime {
+    /// A calendar date.
+    Date(Date),
+    /// A time of day.
+    Time(Time),
+    /// A calendar date with a time of day.
+    Datetime(Date, Time),
+}
+
+impl Datetime {
+    /// Create a date from a year, month and day.
+    pub fn from_ymd(year: i32, month: u8, day: u8) -> Option<Self> {
+        Date::new(year, month, day).map(Self::Date)
+    }
+
+    /// Create a time from an hour, minute and second.
+    pub fn from_hms(hour: u8, minute: u8, second: u8) -> Option<Self> {
+        Time::new(hour, minute, second).map(Self::Time)
+    }
+
+    /// Create a combined date and time.
+    pub fn from_ymd_hms(
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Option<Self> {
+        Some(Self::Datetime(
+            Date::new(year, month, day)?,
+            Time::new(hour, minute, second)?,
+        ))
+    }
+
+    /// The date part, if any.
+    pub fn date(&self) -> Option<Date> {
+        match *self {
+            Self::Date(date) | Self::Datetime(date, _) => Some(date),
+            Self::Time(_) => None,
+        }
+    }
+
+    /// The time part, if any.
+    pub fn time(&self) -> Option<Time> {
+        match *self {
+            Self::Time(time) | Self::Datetime(_, time) => Some(time),
+            Self::Date(_) => None,
+        }
+    }
+
+    /// The year, if this has a date.
+    pub fn year(&self) -> Option<i32> {
+        self.date().map(|date| date.year)
+    }
+
+    /// The month from 1 to 12, if this has a date.
+    pub fn month(&self) -> Option<u8> {
+        self.date().map(|date| date.month)
+    }
+
+    /// The day of the month, if this has a date.
+    pub fn day(&self) -> Option<u8> {
+        self.date().map(|date| date.day)
+    }
+
+    /// The weekday from 1 (Monday) to 7 (Sunday), if this has a date.
+    pub fn weekday(&self) -> Option<u8> {
+        self.date().map(Date::weekday)
+    }
+
+    /// The hour from 0 to 23, if this has a time.
+    pub fn hour(&self) -> Option<u8> {
+        self.time().map(|time| time.hour)
+    }
+
+    /// The minute, if this has a time.
+    pub fn minute(&self) -> Option<u8> {
+        self.time().map(|time| time.minute)
+    }
+
+    /// The second, if this has a time.
+    pub fn second(&self) -> Option<u8> {
+        self.time().map(|time| time.second)
+    }
+
+    /// Add a duration.
+    ///
+    /// Times wrap around at midnight. Dates can only be moved by whole days.
+    pub fn checked_add(self, duration: Duration) -> StrResult<Self> {
+        let seconds = duration.whole_seconds();
+        Ok(match self {
+            Self::Date(date) => {
+                if seconds % DAY != 0 {
+                    Err("cannot add a duration with a time component to a date")?;
+                }
+                Self::Date(date.add_days(seconds / DAY)?)
+            }
+            Self::Time(time) => {
+                let total = time.seconds() + seconds.rem_euclid(DAY);
+                Self::Time(Time::from_seconds(total % DAY))
+            }
+            Self::Datetime(date, time) => {
+                let total = time.seconds() + seconds.rem_euclid(DAY);
+                Self::Datetime(
+                    date.add_days(seconds.div_euclid(DAY) + total / DAY)?,
+                    Time::from_seconds(total % DAY),
+                )
+            }
+        })
+    }
+
+    /// The duration from `other` to `self`.
+    pub fn difference(self, other: Self) -> StrResult<Duration> {
+        let seconds = match (self, other) {
+            (Self::Date(a), Self::Date(b)) => (a.days() - b.days()) * DAY,
+            (Self::Time(a), Self::Time(b)) => a.seconds() - b.seconds(),
+            (Self::Datetime(a, x), Self::Datetime(b, y)) => {
+                (a.days() - b.days()) * DAY + x.seconds() - y.seconds()
+            }
+            _ => {
+                Err(eco_format!("cannot subtract {} from {}", other.kind(), self.kind()))?
+            }
+        };
+        Ok(Duration::from_seconds(seconds))
+    }
+
+    /// Display the datetime according to a pattern.
+    ///
+    /// The pattern consists of literal text and components in brackets like
+    /// `[month repr:long]`. A literal bracket is written as `[[`. Without a
+    /// pattern, an ISO 8601-like format is used.
+    pub fn display(&self, pattern: Option<&str>) -> StrResult<EcoString> {
+        let pattern = pattern.unwrap_or(match self {
+            Self::Date(_) => "[year]-[month]-[day]",
+            Self::Time(_) => "[hour]:[minute]:[second]",
+            Self::Datetime(..) => "[year]-[month]-[day] [hour]:[minute]:[second]",
+        });
+
+        let mut out = EcoString::new();
+        let mut rest = pattern;
+        while let Some(i) = rest.find('[') {
+            out.push_str(&rest[..i]);
+            rest = &rest[i + 1..];
+            if let Some(tail) = rest.strip_prefix('[') {
+                out.push('[');
+                rest = tail;
+                continue;
+            }
+
+            let end = rest.find(']').ok_or("unclosed component in pattern")?;
+            self.component(&rest[..end], &mut out)?;
+            rest = &rest[end + 1..];
+        }
+
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    /// Write a single component of a display pattern.
+    fn component(&self, spec: &str, out: &mut EcoString) -> StrResult<()> {
+        let mut parts = spec.split_whitespace();
+        let name = parts.next().ok_or("missing component name in pattern")?;
+        let allowed: &[&str] = match name {
+            "year" | "month" | "hour" => &["repr", "padding"],
+            "day" | "minute" | "second" => &["padding"],
+            "weekday" => &["repr", "one_indexed"],
+            "period" => &["case"],
+            _ => Err(eco_format!("unknown component `{name}`"))?,
+        };
+
+        let mut modifiers = vec![];
+        for part in parts {
+            let Some((key, value)) = part.split_once(':') else {
+                return Err(eco_format!("invalid modifier `{part}`"));
+            };
+            if !allowed.contains(&key) {
+                Err(eco_format!("component `{name}` has no modifier `{key}`"))?;
+            }
+            modifiers.push((key, value));
+        }
+
+        let modifier = |key: &str, default: &'static str| {
+            modifiers
+                .iter()
+                .rev()
+                .find(|&&(k, _)| k == key)
+                .map_or(default, |&(_, v)| v)
+        };
+
+        let invalid = |key: &str| {
+            eco_format!("invalid value `{}` for modifier `{key}`", modifier(key, ""))
+        };
+
+        let missing = || eco_format!("cannot display the {name} of a {}", self.kind());
+
+        let padding = match modifier("padding", "zero") {
+            "zero" => Padding::Zero,
+            "space" => Padding::Space,
+            "none" => Padding::None,
+            _ => Err(invalid("padding"))?,
+        };
+
+        match name {
+            "year" => {
+                let year = i64::from(self.year().ok_or_else(missing)?);
+                match modifier("repr", "full") {
+                    "full" => padding.write(out, year, 4),
+                    "last_two" => padding.write(out, year.rem_euclid(100), 2),
+                    _ => Err(invalid("repr"))?,
+                }
+            }
+            "month" => {
+                let month = self.month().ok_or_else(missing)?;
+                let long = MONTHS[usize::from(month) - 1];
+                match modifier("repr", "numerical") {
+                    "numerical" => padding.write(out, month.into(), 2),
+                    "long" => out.push_str(long),
+                    "short" => out.push_str(&long[..3]),
+                    _ => Err(invalid("repr"))?,
+                }
+            }
+            "day" => padding.write(out, self.day().ok_or_else(missing)?.into(), 2),
+            "weekday" => {
+                let weekday = self.weekday().ok_or_else(missing)?;
+                let long = WEEKDAYS[usize::from(weekday) - 1];
+                let offset = match modifier("one_indexed", "true") {
+                    "true" => 1,
+                    "false" => 0,
+                    _ => Err(invalid("one_indexed"))?,
+                };
+                match modifier("repr", "long") {
+                    "long" => out.push_str(long),
+                    "short" => out.push_str(&long[..3]),
+                    "monday" => out.push_str(&eco_format!("{}", weekday - 1 + offset)),
+                    "sunday" => out.push_str(&eco_format!("{}", weekday % 7 + offset)),
+                    _ => Err(invalid("repr"))?,
+                }
+            }
+            "hour" => {
+                let hour = self.hour().ok_or_else(missing)?;
+                match modifier("repr", "24") {
+                    "24" => padding.write(out, hour.into(), 2),
+                    "12" => padding.write(out, ((hour + 11) % 12 + 1).into(), 2),
+                    _ => Err(invalid("repr"))?,
+                }
+            }
+            "minute" => padding.write(out, self.minute().ok_or_else(missing)?.into(), 2),
+            "second" => padding.write(out, self.second().ok_or_else(missing)?.into(), 2),
+            "period" => {
+                let am = self.hour().ok_or_else(missing)? < 12;
+                match (modifier("case", "upper"), am) {
+                    ("upper", true) => out.push_str("AM"),
+                    ("upper", false) => out.push_str("PM"),
+                    ("lower", true) => out.push_str("am"),
+                    ("lower", false) => out.push_str("pm"),
+                    _ => Err(invalid("case"))?,
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    /// The name of this kind of datetime.
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Date(_) => "date",
+            Self::Time(_) => "time",
+            Self::Datetime(..) => "datetime",
+        }
+    }
+}
+
+impl PartialOrd for Datetime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Date(a), Self::Date(b)) => a.partial_cmp(b),
+            (Self::Time(a), Self::Time(b)) => a.partial_cmp(b),
+            (Self::Datetime(a, x), Self::Datetime(b, y)) => (a, x).partial_cmp(&(b, y)),
+            _ => None,
+        }
+    }
+}
+
+impl Debug for Datetime {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let date = self.date().map(|Date { year, month, day }| {
+            eco_format!("year: {year}, month: {month}, day: {day}")
+        });
+        let time = self.time().map(|Time { hour, minute, second }| {
+            eco_format!("hour: {hour}, minute: {minute}, second: {second}")
+        });
+        let parts: Vec<_> = date.into_iter().chain(time).collect();
+        write!(f, "datetime({})", parts.join(", "))
+    }
+}
+
+/// A date in the proleptic Gregorian calendar.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Create a date, if the day exists.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => return None,
+        };
+        (1..=days).contains(&day).then_some(Self { year, month, day })
+    }
+
+    /// The number of days since 1970-01-01.
+    fn days(self) -> i64 {
+        // Counts from March so that the leap day is at the end of the year.
+        let month = i64::from(self.month);
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era =
+            year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    /// The date a number of days after 1970-01-01.
+    fn from_days(days: i64) -> Option<Self> {
+        let days = days.checked_add(719468)?;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524
+            - day_of_era / 146096)
+            / 365;
+        let day_of_year =
+            day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = (shifted_month + 2) % 12 + 1;
+        let year = era * 400 + year_of_era + i64::from(month <= 2);
+        Some(Self {
+            year: year.try_into().ok()?,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// Move the date by a number of days.
+    fn add_days(self, days: i64) -> StrResult<Self> {
+        self.days()
+            .checked_add(days)
+            .and_then(Self::from_days)
+            .ok_or_else(|| "date is out of range".into())
+    }
+
+    /// The weekday from 1 (Monday) to 7 (Sunday).
+    fn weekday(self) -> u8 {
+        // 1970-01-01 was a Thursday.
+        ((self.days() + 3).rem_euclid(7) + 1) as u8
+    }
+}
+
+/// A time of day with a precision of one second.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl Time {
+    /// Create a time, if the components are in range.
+    pub fn new(hour: u8, minute: u8, second: u8) -> Option<Self> {
+        (hour < 24 && minute < 60 && second < 60).then_some(Self { hour, minute, second })
+    }
+
+    /// The number of seconds since midnight.
+    fn seconds(self) -> i64 {
+        i64::from(self.hour) * 3600 + i64::from(self.minute) * 60 + i64::from(self.second)
+    }
+
+    /// The time a number of seconds after midnight, which must be less than
+    /// a day.
+    fn from_seconds(seconds: i64) -> Self {
+        debug_assert!((0..DAY).contains(&seconds));
+        Self {
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+        }
+    }
+}
+
+/// How to pad a number to a minimum width.
+#[derive(Copy, Clone)]
+enum Padding {
+    Zero,
+    Space,
+    None,
+}
+
+impl Padding {
+    /// Write a number with this padding.
+    fn write(self, out: &mut EcoString, value: i64, width: usize) {
+        out.push_str(&match self {
+            Self::Zero => eco_format!("{value:0width$}"),
+            Self::Space => eco_format!("{value:width$}"),
+            Self::None => eco_format!("{value}"),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_datetime_days() {
+        for (year, month, day, days) in [
+            (1970, 1, 1, 0),
+            (1969, 12, 31, -1),
+            (2000, 3, 1, 11017),
+            (2023, 5, 22, 19499),
+            (1600, 2, 29, -135081),
+        ] {
+            let date = Date::new(year, month, day).unwrap();
+            assert_eq!(date.days(), days);
+            assert_eq!(Date::from_days(days), Some(date));
+        }
+    }
+
+    #[test]
+    fn test_datetime_display() {
+        let date = Datetime::from_ymd_hms(2023, 5, 1, 14, 3, 9).unwrap();
+        let test = |pattern, exp: &str| {
+            assert_eq!(date.display(Some(pattern)).unwrap(), exp);
+        };
+
+        test("[day]. [month repr:long] [year]", "01. May 2023");
+        test("[weekday repr:short], [year repr:last_two]", "Mon, 23");
+        test("[hour repr:12 padding:none]:[minute] [period case:lower]", "2:03 pm");
+        test("[[[day padding:space]]", "[ 1]");
+        assert!(date.display(Some("[hours]")).is_err());
+        assert!(Datetime::from_hms(1, 2, 3).unwrap().display(Some("[day]")).is_err());
+    }
+}
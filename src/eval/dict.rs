@@ -5,8 +5,8 @@ use std::sync::Arc;
 
 use ecow::{eco_format, EcoString};
 
-use super::{array, Array, Str, Value};
-use crate::diag::StrResult;
+use super::{array, Args, Array, Func, Str, Value, Vm};
+use crate::diag::{At, SourceResult, StrResult};
 use crate::syntax::is_ident;
 use crate::util::{pretty_array_like, separated_list, ArcExt};
 
@@ -115,6 +115,37 @@ impl Dict {
             .collect()
     }
 
+    /// Return a new dictionary with only those pairs for which the function
+    /// returns true. The function receives the key and the value.
+    pub fn filter(&self, vm: &mut Vm, func: Func) -> SourceResult<Self> {
+        let mut kept = BTreeMap::new();
+        for (key, value) in self.iter() {
+            let args = Args::new(func.span(), [Value::Str(key.clone()), value.clone()]);
+            if func.call_vm(vm, args)?.cast::<bool>().at(func.span())? {
+                kept.insert(key.clone(), value.clone());
+            }
+        }
+        Ok(Self::from_map(kept))
+    }
+
+    /// Transform each value in the dictionary with a function. The function
+    /// receives the key and the value.
+    pub fn map(&self, vm: &mut Vm, func: Func) -> SourceResult<Self> {
+        self.iter()
+            .map(|(key, value)| {
+                let args =
+                    Args::new(func.span(), [Value::Str(key.clone()), value.clone()]);
+                Ok((key.clone(), func.call_vm(vm, args)?))
+            })
+            .collect()
+    }
+
+    /// Return a new dictionary with the pairs of both dictionaries. Pairs of
+    /// the other dictionary take precedence.
+    pub fn merge(&self, other: Dict) -> Self {
+        self.clone() + other
+    }
+
     /// Iterate over pairs of references to the contained keys and values.
     pub fn iter(&self) -> std::collections::btree_map::Iter<Str, Value> {
         self.0.iter()
@@ -0,0 +1,133 @@
+use std::fmt::{self, Debug, Formatter};
+
+use crate::diag::StrResult;
+
+/// A span of time with a precision of one second.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Duration(i64);
+
+impl Duration {
+    /// The number of seconds in a minute.
+    const MINUTE: i64 = 60;
+    /// The number of seconds in an hour.
+    const HOUR: i64 = 60 * Self::MINUTE;
+    /// The number of seconds in a day.
+    const DAY: i64 = 24 * Self::HOUR;
+    /// The number of seconds in a week.
+    const WEEK: i64 = 7 * Self::DAY;
+
+    /// Create a duration from its components, failing if it is too long.
+    pub fn new(
+        weeks: i64,
+        days: i64,
+        hours: i64,
+        minutes: i64,
+        seconds: i64,
+    ) -> StrResult<Self> {
+        [
+            (weeks, Self::WEEK),
+            (days, Self::DAY),
+            (hours, Self::HOUR),
+            (minutes, Self::MINUTE),
+            (seconds, 1),
+        ]
+        .into_iter()
+        .try_fold(0i64, |sum, (count, unit)| sum.checked_add(count.checked_mul(unit)?))
+        .map(Self)
+        .ok_or_else(|| "duration is too long".into())
+    }
+
+    /// Create a duration from a number of seconds.
+    pub const fn from_seconds(seconds: i64) -> Self {
+        Self(seconds)
+    }
+
+    /// The whole number of seconds in the duration.
+    pub const fn whole_seconds(self) -> i64 {
+        self.0
+    }
+
+    /// The duration in seconds.
+    pub fn seconds(self) -> f64 {
+        self.0 as f64
+    }
+
+    /// The duration in minutes.
+    pub fn minutes(self) -> f64 {
+        self.0 as f64 / Self::MINUTE as f64
+    }
+
+    /// The duration in hours.
+    pub fn hours(self) -> f64 {
+        self.0 as f64 / Self::HOUR as f64
+    }
+
+    /// The duration in days.
+    pub fn days(self) -> f64 {
+        self.0 as f64 / Self::DAY as f64
+    }
+
+    /// The duration in weeks.
+    pub fn weeks(self) -> f64 {
+        self.0 as f64 / Self::WEEK as f64
+    }
+
+    /// Negate the duration, failing on overflow.
+    pub fn checked_neg(self) -> StrResult<Self> {
+        self.0
+            .checked_neg()
+            .map(Self)
+            .ok_or_else(|| "duration is too long".into())
+    }
+
+    /// Add another duration, failing on overflow.
+    pub fn checked_add(self, other: Self) -> StrResult<Self> {
+        self.0
+            .checked_add(other.0)
+            .map(Self)
+            .ok_or_else(|| "duration is too long".into())
+    }
+
+    /// Subtract another duration, failing on overflow.
+    pub fn checked_sub(self, other: Self) -> StrResult<Self> {
+        self.0
+            .checked_sub(other.0)
+            .map(Self)
+            .ok_or_else(|| "duration is too long".into())
+    }
+
+    /// Multiply the duration with a factor, failing on overflow.
+    pub fn checked_mul(self, factor: i64) -> StrResult<Self> {
+        self.0
+            .checked_mul(factor)
+            .map(Self)
+            .ok_or_else(|| "duration is too long".into())
+    }
+}
+
+impl Debug for Duration {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let units = [
+            ("days", Self::DAY),
+            ("hours", Self::HOUR),
+            ("minutes", Self::MINUTE),
+            ("seconds", 1),
+        ];
+
+        let mut rest = self.0;
+        let mut parts = vec![];
+        for (name, unit) in units {
+            let count = rest / unit;
+            rest %= unit;
+            if count != 0 {
+                parts.push(format!("{name}: {count}"));
+            }
+        }
+
+        if parts.is_empty() {
+            parts.push("seconds: 0".into());
+        }
+
+        write!(f, "duration({})", parts.join(", "))
+    }
+}
@@ -5,15 +5,17 @@ use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use comemo::{Prehashed, Track, Tracked, TrackedMut};
+use ecow::eco_format;
 use once_cell::sync::Lazy;
 
 use super::{
     cast_to_value, Args, CastInfo, Eval, Flow, Route, Scope, Scopes, Tracer, Value, Vm,
 };
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, SourceResult, StrResult};
 use crate::model::{ElemFunc, Introspector, StabilityProvider, Vt};
 use crate::syntax::ast::{self, AstNode, Expr, Ident};
 use crate::syntax::{SourceId, Span, SyntaxNode};
+use crate::util::timing;
 use crate::World;
 
 /// An evaluatable function.
@@ -102,6 +104,7 @@ impl Func {
                 let route =
                     if vm.location.is_detached() { fresh.track() } else { vm.route };
 
+                timing::memo_call("closure");
                 Closure::call(
                     self,
                     vm.world(),
@@ -147,6 +150,14 @@ impl Func {
             _ => None,
         }
     }
+
+    /// Get a field from the function's scope.
+    pub fn get(&self, field: &str) -> StrResult<&Value> {
+        let info = self.info().ok_or("cannot access fields on user-defined functions")?;
+        info.scope.get(field).ok_or_else(|| {
+            eco_format!("function `{}` does not contain `{field}`", info.name)
+        })
+    }
 }
 
 impl Debug for Func {
@@ -228,6 +239,8 @@ pub struct FuncInfo {
     pub returns: Vec<&'static str>,
     /// Which category the function is part of.
     pub category: &'static str,
+    /// Definitions that are accessible as fields of the function.
+    pub scope: Scope,
 }
 
 impl FuncInfo {
@@ -259,6 +272,8 @@ pub struct ParamInfo {
     pub required: bool,
     /// Is the parameter settable with a set rule?
     pub settable: bool,
+    /// Produces the default value of the parameter, if it is known.
+    pub default: Option<fn() -> Value>,
 }
 
 /// A user-defined closure.
@@ -297,6 +312,8 @@ impl Closure {
             _ => panic!("`this` must be a closure"),
         };
 
+        timing::memo_miss("closure");
+
         // Don't leak the scopes from the call site. Instead, we use the scope
         // of captured variables we collected earlier.
         let mut scopes = Scopes::new(None);
@@ -439,10 +456,12 @@ impl<'a> CapturesVisitor<'a> {
                 if let Some(init) = expr.init() {
                     self.visit(init.as_untyped());
                 }
-                self.bind(expr.binding());
+                for ident in expr.pattern().idents() {
+                    self.bind(ident);
+                }
             }
 
-            // A for loop contains one or two bindings in its pattern. These are
+            // A for loop contains one or more bindings in its pattern. These are
             // active after the iterable is evaluated but before the body is
             // evaluated.
             Some(ast::Expr::For(expr)) => {
@@ -452,7 +471,9 @@ impl<'a> CapturesVisitor<'a> {
                 if let Some(key) = pattern.key() {
                     self.bind(key);
                 }
-                self.bind(pattern.value());
+                for ident in pattern.value().idents() {
+                    self.bind(ident);
+                }
                 self.visit(expr.body().as_untyped());
                 self.internal.exit();
             }
@@ -461,9 +482,12 @@ impl<'a> CapturesVisitor<'a> {
             // path is evaluated.
             Some(ast::Expr::Import(expr)) => {
                 self.visit(expr.source().as_untyped());
+                if let Some(new_name) = expr.new_name() {
+                    self.bind(new_name);
+                }
                 if let Some(ast::Imports::Items(items)) = expr.imports() {
                     for item in items {
-                        self.bind(item);
+                        self.bind(item.bound_name());
                     }
                 }
             }
@@ -533,6 +557,9 @@ mod tests {
         test("#let f(x, y) = x + y", &[]);
         test("#let f(x, y) = f", &[]);
         test("#let f = (x, y) => f", &["f"]);
+        test("#let (x, y) = (y, z)", &["y", "z"]);
+        test("#let (x, ..y) = x; #(x + y + z)", &["x", "z"]);
+        test("#let (a: x, y) = z; #(a + x + y)", &["z"]);
 
         // Closure with different kinds of params.
         test("#((x, y) => x + z)", &["z"]);
@@ -550,10 +577,14 @@ mod tests {
         test("#for x in y { x + z }", &["y", "z"]);
         test("#for x, y in y { x + y }", &["y"]);
         test("#for x in y {} #x", &["x", "y"]);
+        test("#for (x, y) in y { x + y + z }", &["y", "z"]);
+        test("#for (x, (y: z)) in z { y + z }", &["y", "z"]);
 
         // Import.
         test("#import z: x, y", &["z"]);
         test("#import x + y: x, y, z", &["x", "y"]);
+        test("#import z as x: y; #(x + y + z)", &["z"]);
+        test("#import z: x as y; #(x + y)", &["x", "z"]);
 
         // Blocks.
         test("#{ let x = 1; { let y = 2; y }; x + y }", &["y"]);
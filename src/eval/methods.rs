@@ -70,6 +70,44 @@ pub fn call(
             _ => return missing(),
         },
 
+        Value::Bytes(bytes) => match method {
+            "len" => Value::Int(bytes.len()),
+            "at" => Value::Int(bytes.at(args.expect("index")?).at(span)?.into()),
+            "slice" => {
+                let start = args.expect("start")?;
+                let mut end = args.eat()?;
+                if end.is_none() {
+                    end = args.named("count")?.map(|c: i64| start + c);
+                }
+                Value::Bytes(bytes.slice(start, end).at(span)?)
+            }
+            _ => return missing(),
+        },
+
+        Value::Datetime(datetime) => match method {
+            "display" => {
+                let pattern = args.eat::<EcoString>()?;
+                Value::Str(datetime.display(pattern.as_deref()).at(span)?.into())
+            }
+            "year" => datetime.year().into(),
+            "month" => datetime.month().into(),
+            "day" => datetime.day().into(),
+            "weekday" => datetime.weekday().into(),
+            "hour" => datetime.hour().into(),
+            "minute" => datetime.minute().into(),
+            "second" => datetime.second().into(),
+            _ => return missing(),
+        },
+
+        Value::Duration(duration) => match method {
+            "seconds" => Value::Float(duration.seconds()),
+            "minutes" => Value::Float(duration.minutes()),
+            "hours" => Value::Float(duration.hours()),
+            "days" => Value::Float(duration.days()),
+            "weeks" => Value::Float(duration.weeks()),
+            _ => return missing(),
+        },
+
         Value::Content(content) => match method {
             "func" => content.func().into(),
             "has" => Value::Bool(content.has(&args.expect::<EcoString>("field")?)),
@@ -115,16 +153,33 @@ pub fn call(
                 let last = args.named("last")?;
                 array.join(sep, last).at(span)?
             }
-            "sorted" => Value::Array(array.sorted().at(span)?),
+            "sorted" => Value::Array(array.sorted(vm, span, args.named("key")?)?),
+            "zip" => Value::Array(array.zip(&args.expect("other")?)),
+            "enumerate" => Value::Array(array.enumerate()),
+            "sum" => array.sum(args.named("default")?).at(span)?,
+            "product" => array.product(args.named("default")?).at(span)?,
+            "dedup" => Value::Array(array.dedup()),
+            "chunks" => Value::Array(array.chunks(args.expect("chunk size")?)),
+            "windows" => Value::Array(array.windows(args.expect("window size")?)),
+            "intersperse" => Value::Array(array.intersperse(args.expect("separator")?)),
             _ => return missing(),
         },
 
         Value::Dict(dict) => match method {
             "len" => Value::Int(dict.len()),
-            "at" => dict.at(&args.expect::<Str>("key")?).at(span)?.clone(),
+            "at" => {
+                let key = args.expect::<Str>("key")?;
+                match args.named::<Value>("default")? {
+                    Some(default) => dict.at(&key).cloned().unwrap_or(default),
+                    None => dict.at(&key).at(span)?.clone(),
+                }
+            }
             "keys" => Value::Array(dict.keys()),
             "values" => Value::Array(dict.values()),
             "pairs" => Value::Array(dict.pairs()),
+            "filter" => Value::Dict(dict.filter(vm, args.expect("function")?)?),
+            "map" => Value::Dict(dict.map(vm, args.expect("function")?)?),
+            "merge" => Value::Dict(dict.merge(args.expect("other")?)),
             _ => return missing(),
         },
 
@@ -272,36 +327,65 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("starts-with", true),
             ("trim", true),
         ],
+        "bytes" => &[("len", false), ("at", true), ("slice", true)],
+        "datetime" => &[
+            ("day", false),
+            ("display", true),
+            ("hour", false),
+            ("minute", false),
+            ("month", false),
+            ("second", false),
+            ("weekday", false),
+            ("year", false),
+        ],
+        "duration" => &[
+            ("days", false),
+            ("hours", false),
+            ("minutes", false),
+            ("seconds", false),
+            ("weeks", false),
+        ],
         "content" => &[("func", false), ("has", true), ("at", true), ("location", false)],
         "array" => &[
             ("all", true),
             ("any", true),
             ("at", true),
+            ("chunks", true),
             ("contains", true),
+            ("dedup", false),
+            ("enumerate", false),
             ("filter", true),
             ("find", true),
             ("first", false),
             ("flatten", false),
             ("fold", true),
             ("insert", true),
-            ("split", true),
+            ("intersperse", true),
             ("join", true),
             ("last", false),
             ("len", false),
             ("map", true),
             ("pop", false),
             ("position", true),
+            ("product", false),
             ("push", true),
             ("remove", true),
             ("rev", false),
             ("slice", true),
-            ("sorted", false),
+            ("sorted", true),
+            ("split", true),
+            ("sum", false),
+            ("windows", true),
+            ("zip", true),
         ],
         "dictionary" => &[
             ("at", true),
+            ("filter", true),
             ("insert", true),
             ("keys", false),
             ("len", false),
+            ("map", true),
+            ("merge", true),
             ("pairs", false),
             ("remove", true),
             ("values", false),
@@ -317,6 +401,7 @@ pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
             ("update", true),
         ],
         "state" => &[("display", true), ("at", true), ("final", true), ("update", true)],
+        "rng" => &[("int", true), ("float", false), ("choice", true), ("shuffle", true)],
         _ => &[],
     }
 }
@@ -13,10 +13,14 @@ mod str;
 #[macro_use]
 mod value;
 mod args;
+mod bytes;
+mod datetime;
+mod duration;
 mod func;
 mod methods;
 mod module;
 mod ops;
+mod package;
 mod scope;
 mod symbol;
 
@@ -25,11 +29,15 @@ pub use once_cell::sync::Lazy;
 
 pub use self::args::*;
 pub use self::array::*;
+pub use self::bytes::*;
 pub use self::cast::*;
+pub use self::datetime::*;
 pub use self::dict::*;
+pub use self::duration::*;
 pub use self::func::*;
 pub use self::library::*;
 pub use self::module::*;
+pub use self::package::*;
 pub use self::scope::*;
 pub use self::str::*;
 pub use self::symbol::*;
@@ -37,7 +45,7 @@ pub use self::value::*;
 
 pub(crate) use self::methods::methods_on;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::mem;
 use std::path::{Path, PathBuf};
 
@@ -46,17 +54,17 @@ use ecow::EcoVec;
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::diag::{
-    bail, error, At, SourceError, SourceResult, StrResult, Trace, Tracepoint,
+    bail, error, warning, At, SourceError, SourceResult, StrResult, Trace, Tracepoint,
 };
 use crate::model::{
-    Content, Introspector, Label, Recipe, Selector, StabilityProvider, Styles, Transform,
-    Unlabellable, Vt,
+    Content, Introspector, Label, Location, Recipe, Selector, StabilityProvider, Styles,
+    Transform, Unlabellable, Vt,
 };
 use crate::syntax::ast::AstNode;
 use crate::syntax::{
     ast, parse_code, Source, SourceId, Span, Spanned, SyntaxKind, SyntaxNode,
 };
-use crate::util::PathExt;
+use crate::util::{timing, PathExt};
 use crate::World;
 
 const MAX_ITERATIONS: usize = 10_000;
@@ -77,6 +85,9 @@ pub fn eval(
         panic!("Tried to cyclicly evaluate {}", path.display());
     }
 
+    timing::memo_miss("eval");
+    let _scope = timing::scope("eval", || path.display().to_string().into());
+
     // Hook up the lang items.
     let library = world.library();
     set_lang_items(library.items.clone());
@@ -216,7 +227,8 @@ impl<'a> Vm<'a> {
     pub fn locate(&self, path: &str) -> StrResult<PathBuf> {
         if !self.location.is_detached() {
             if let Some(path) = path.strip_prefix('/') {
-                return Ok(self.world().root().join(path).normalize());
+                let root = self.world().root_of(self.location);
+                return Ok(root.join(path).normalize());
             }
 
             if let Some(dir) = self.world().source(self.location).path().parent() {
@@ -290,11 +302,17 @@ impl Route {
     }
 }
 
-/// Traces which values existed for the expression at a span.
+/// Traces which values existed for the expression at a span and collects the
+/// warnings that occurred during compilation.
 #[derive(Default, Clone)]
 pub struct Tracer {
     span: Option<Span>,
     values: Vec<Value>,
+    warnings: Vec<SourceError>,
+    labels: Vec<(Label, Span)>,
+    referenced: HashSet<Label>,
+    introspections: HashMap<Location, u128>,
+    unstable: Vec<Span>,
 }
 
 impl Tracer {
@@ -303,13 +321,30 @@ impl Tracer {
 
     /// Create a new tracer, possibly with a span under inspection.
     pub fn new(span: Option<Span>) -> Self {
-        Self { span, values: vec![] }
+        Self { span, ..Self::default() }
     }
 
     /// Get the traced values.
     pub fn finish(self) -> Vec<Value> {
         self.values
     }
+
+    /// The warnings collected so far.
+    pub fn warnings(&self) -> &[SourceError] {
+        &self.warnings
+    }
+
+    /// Take the collected warnings, adding warnings for labels that were
+    /// attached to content, but never referenced.
+    pub fn take_warnings(&mut self) -> Vec<SourceError> {
+        let mut seen = HashSet::new();
+        for (label, span) in mem::take(&mut self.labels) {
+            if !self.referenced.contains(&label) && seen.insert(label.clone()) {
+                self.warn(warning!(span, "label `{:?}` is never referenced", label));
+            }
+        }
+        mem::take(&mut self.warnings)
+    }
 }
 
 #[comemo::track]
@@ -329,6 +364,62 @@ impl Tracer {
             self.values.push(v);
         }
     }
+
+    /// Report a warning.
+    ///
+    /// Since layout may run multiple times, identical warnings are only
+    /// reported once.
+    pub fn warn(&mut self, warning: SourceError) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Record the hashed result of an introspection at a location.
+    ///
+    /// If the result differs from the one of the previous layout iteration, the
+    /// introspection at the given span is considered unstable.
+    pub fn introspect(&mut self, location: Location, span: Span, hash: u128) {
+        let prev = self.introspections.insert(location, hash);
+        if prev.map_or(false, |prev| prev != hash) && !self.unstable.contains(&span) {
+            self.unstable.push(span);
+        }
+    }
+
+    /// Start a new layout iteration.
+    pub fn relayout(&mut self) {
+        self.unstable.clear();
+    }
+
+    /// Report that layout did not converge within the given number of
+    /// iterations, pointing at the introspections that kept changing.
+    pub fn diverged(&mut self, iterations: usize) {
+        if self.unstable.is_empty() {
+            self.warn(warning!(
+                Span::detached(),
+                "layout did not converge within {} attempts",
+                iterations,
+            ));
+        }
+
+        for span in mem::take(&mut self.unstable) {
+            self.warn(warning!(
+                span,
+                "introspection did not converge within {} layout attempts",
+                iterations,
+            ));
+        }
+    }
+
+    /// Record that a label was attached to content at the given span.
+    fn label(&mut self, label: Label, span: Span) {
+        self.labels.push((label, span));
+    }
+
+    /// Record that a label was referenced or otherwise used in code.
+    pub fn reference(&mut self, label: Label) {
+        self.referenced.insert(label);
+    }
 }
 
 /// Evaluate an expression.
@@ -375,8 +466,19 @@ fn eval_markup(
                 let tail = eval_markup(vm, exprs)?;
                 seq.push(tail.styled_with_recipe(vm, recipe)?)
             }
+            ast::Expr::Label(label) => {
+                let span = label.span();
+                let label = Label(label.get().into());
+                vm.vt.tracer.label(label.clone(), span);
+                if let Some(elem) =
+                    seq.iter_mut().rev().find(|node| !node.can::<dyn Unlabellable>())
+                {
+                    *elem = mem::take(elem).labelled(label);
+                }
+            }
             expr => match expr.eval(vm)? {
                 Value::Label(label) => {
+                    vm.vt.tracer.label(label.clone(), expr.span());
                     if let Some(elem) =
                         seq.iter_mut().rev().find(|node| !node.can::<dyn Unlabellable>())
                     {
@@ -573,8 +675,10 @@ impl Eval for ast::Link {
 impl Eval for ast::Label {
     type Output = Value;
 
-    fn eval(&self, _: &mut Vm) -> SourceResult<Self::Output> {
-        Ok(Value::Label(Label(self.get().into())))
+    fn eval(&self, vm: &mut Vm) -> SourceResult<Self::Output> {
+        let label = Label(self.get().into());
+        vm.vt.tracer.reference(label.clone());
+        Ok(Value::Label(label))
     }
 }
 
@@ -583,6 +687,7 @@ impl Eval for ast::Ref {
 
     fn eval(&self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let label = Label(self.target().into());
+        vm.vt.tracer.reference(label.clone());
         let supplement = self.supplement().map(|block| block.eval(vm)).transpose()?;
         Ok((vm.items.reference)(label, supplement))
     }
@@ -1009,7 +1114,8 @@ impl Eval for ast::FuncCall {
         let args = self.args();
 
         // Try to evaluate as a method call. This is possible if the callee is a
-        // field access and does not evaluate to a module.
+        // field access and does not evaluate to a module, a symbol or a function
+        // with a field of that name.
         let (callee, mut args) = if let ast::Expr::FieldAccess(access) = callee {
             let target = access.target();
             let field = access.field();
@@ -1030,7 +1136,12 @@ impl Eval for ast::FuncCall {
             } else {
                 let target = target.eval(vm)?;
                 let args = args.eval(vm)?;
-                if !matches!(target, Value::Symbol(_) | Value::Module(_)) {
+                let is_field = match &target {
+                    Value::Symbol(_) | Value::Module(_) => true,
+                    Value::Func(func) => func.get(&field).is_ok(),
+                    _ => false,
+                };
+                if !is_field {
                     return methods::call(vm, target, &field, args, span).trace(
                         vm.world(),
                         point,
@@ -1192,11 +1303,118 @@ impl Eval for ast::LetBinding {
             Some(expr) => expr.eval(vm)?,
             None => Value::None,
         };
-        vm.define(self.binding(), value);
+        define_pattern(vm, &self.pattern(), value)?;
         Ok(Value::None)
     }
 }
 
+/// Bind a value to a pattern, destructuring arrays and dictionaries.
+fn define_pattern(vm: &mut Vm, pattern: &ast::Pattern, value: Value) -> SourceResult<()> {
+    match pattern {
+        ast::Pattern::Normal(ident) => {
+            vm.define(ident.clone(), value);
+            Ok(())
+        }
+        ast::Pattern::Destructuring(destructuring) => match value {
+            Value::Array(array) => define_array(vm, destructuring, array),
+            Value::Dict(dict) => define_dict(vm, destructuring, dict),
+            value => bail!(pattern.span(), "cannot destructure {}", value.type_name()),
+        },
+    }
+}
+
+/// Destructure an array positionally.
+fn define_array(
+    vm: &mut Vm,
+    destructuring: &ast::Destructuring,
+    array: Array,
+) -> SourceResult<()> {
+    let items: Vec<_> = destructuring.items().collect();
+    let mut sink = false;
+    for item in &items {
+        match item {
+            ast::DestructuringItem::Named(named) => {
+                bail!(named.span(), "cannot destructure named elements from an array")
+            }
+            ast::DestructuringItem::Sink(_) => sink = true,
+            ast::DestructuringItem::Pattern(_) => {}
+        }
+    }
+
+    let expected = items.len() - usize::from(sink);
+    let found = array.len() as usize;
+    if found > expected && !sink {
+        bail!(
+            destructuring.span(),
+            "too many elements to destructure (expected {}, found {})",
+            expected,
+            found,
+        );
+    } else if found < expected {
+        let at_least = if sink { "at least " } else { "" };
+        bail!(
+            destructuring.span(),
+            "not enough elements to destructure (expected {}{}, found {})",
+            at_least,
+            expected,
+            found,
+        );
+    }
+
+    let mut values = array.into_iter();
+    for item in items {
+        match item {
+            ast::DestructuringItem::Pattern(pattern) => {
+                define_pattern(vm, &pattern, values.next().unwrap_or_default())?;
+            }
+            ast::DestructuringItem::Sink(ident) => {
+                let rest: Array = values.by_ref().take(found - expected).collect();
+                vm.define(ident, rest);
+            }
+            ast::DestructuringItem::Named(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Destructure a dictionary by key.
+fn define_dict(
+    vm: &mut Vm,
+    destructuring: &ast::Destructuring,
+    dict: Dict,
+) -> SourceResult<()> {
+    let mut used = HashSet::<Str>::new();
+    let mut sink = None;
+    for item in destructuring.items() {
+        match item {
+            ast::DestructuringItem::Pattern(ast::Pattern::Normal(ident)) => {
+                let value = dict.at(&ident).at(ident.span())?.clone();
+                used.insert(ident.as_str().into());
+                vm.define(ident, value);
+            }
+            ast::DestructuringItem::Pattern(ast::Pattern::Destructuring(nested)) => {
+                bail!(nested.span(), "cannot destructure unnamed pattern from dictionary")
+            }
+            ast::DestructuringItem::Named(named) => {
+                let name = named.name();
+                let value = dict.at(&name).at(name.span())?.clone();
+                used.insert(name.as_str().into());
+                define_pattern(vm, &named.pattern(), value)?;
+            }
+            ast::DestructuringItem::Sink(ident) => sink = Some(ident),
+        }
+    }
+
+    if let Some(ident) = sink {
+        let rest: Dict =
+            dict.into_iter().filter(|(key, _)| !used.contains(key)).collect();
+        vm.define(ident, rest);
+    }
+
+    Ok(())
+}
+
 impl Eval for ast::SetRule {
     type Output = Styles;
 
@@ -1218,7 +1436,7 @@ impl Eval for ast::SetRule {
             })
             .at(target.span())?;
         let args = self.args().eval(vm)?;
-        Ok(target.set(args)?.spanned(self.span()))
+        Ok(target.set(vm, args)?.spanned(self.span()))
     }
 }
 
@@ -1338,7 +1556,7 @@ impl Eval for ast::ForLoop {
 
                 #[allow(unused_parens)]
                 for ($($value),*) in $iter {
-                    $(vm.define($binding.clone(), $value);)*
+                    $(define_pattern(vm, &$binding, $value.into())?;)*
 
                     let body = self.body();
                     let value = body.eval(vm)?;
@@ -1361,10 +1579,17 @@ impl Eval for ast::ForLoop {
 
         let iter = self.iter().eval(vm)?;
         let pattern = self.pattern();
-        let key = pattern.key();
+        let key = pattern.key().map(ast::Pattern::Normal);
         let value = pattern.value();
 
         match (key, value, iter) {
+            (_, ast::Pattern::Destructuring(_), Value::Str(_)) => {
+                bail!(pattern.span(), "mismatched pattern");
+            }
+            (None, v @ ast::Pattern::Destructuring(_), Value::Dict(dict)) => {
+                iter!(for (v => value) in dict.into_iter()
+                    .map(|(k, v)| Value::Array(array![Value::Str(k), v])));
+            }
             (None, v, Value::Str(string)) => {
                 iter!(for (v => value) in string.as_str().graphemes(true));
             }
@@ -1413,22 +1638,30 @@ impl Eval for ast::ModuleImport {
         let source = self.source().eval(vm)?;
         let module = import(vm, source, span)?;
 
+        if let Some(new_name) = self.new_name() {
+            let renamed = module.clone().with_name(new_name.as_str());
+            vm.define(new_name, renamed);
+        }
+
         match self.imports() {
             None => {
-                vm.scopes.top.define(module.name().clone(), module);
+                if self.new_name().is_none() {
+                    vm.scopes.top.define(module.name().clone(), module);
+                }
             }
             Some(ast::Imports::Wildcard) => {
                 for (var, value) in module.scope().iter() {
                     vm.scopes.top.define(var.clone(), value.clone());
                 }
             }
-            Some(ast::Imports::Items(idents)) => {
+            Some(ast::Imports::Items(items)) => {
                 let mut errors = vec![];
-                for ident in idents {
-                    if let Some(value) = module.scope().get(&ident) {
-                        vm.define(ident, value.clone());
+                for item in items {
+                    let original = item.original_name();
+                    if let Some(value) = module.scope().get(&original) {
+                        vm.define(item.bound_name(), value.clone());
                     } else {
-                        errors.push(error!(ident.span(), "unresolved import"));
+                        errors.push(error!(original.span(), "unresolved import"));
                     }
                 }
                 if !errors.is_empty() {
@@ -1460,9 +1693,20 @@ fn import(vm: &mut Vm, source: Value, span: Span) -> SourceResult<Module> {
         v => bail!(span, "expected path or module, found {}", v.type_name()),
     };
 
-    // Load the source file.
+    // Load the source file, which is the entrypoint of the package for paths
+    // like `@local/letter:0.2.0`.
     let world = vm.world();
-    let full = vm.locate(&path).at(span)?;
+    let package = if path.as_str().starts_with('@') {
+        Some(path.parse::<PackageSpec>().at(span)?)
+    } else {
+        None
+    };
+
+    let full = match &package {
+        Some(spec) => spec.entrypoint(world).at(span)?,
+        None => vm.locate(&path).at(span)?,
+    };
+
     let id = world.resolve(&full).at(span)?;
 
     // Prevent cyclic importing.
@@ -1473,8 +1717,16 @@ fn import(vm: &mut Vm, source: Value, span: Span) -> SourceResult<Module> {
     // Evaluate the file.
     let source = world.source(id);
     let point = || Tracepoint::Import;
-    eval(world, vm.route, TrackedMut::reborrow_mut(&mut vm.vt.tracer), source)
-        .trace(world, point, span)
+    timing::memo_call("eval");
+    let module =
+        eval(world, vm.route, TrackedMut::reborrow_mut(&mut vm.vt.tracer), source)
+            .trace(world, point, span)?;
+
+    // A package's module is named after the package.
+    Ok(match package {
+        Some(spec) => module.with_name(spec.name),
+        None => module,
+    })
 }
 
 impl Eval for ast::LoopBreak {
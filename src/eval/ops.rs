@@ -25,6 +25,7 @@ pub fn join(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Str(a), Str(b)) => Str(a + b),
         (Str(a), Symbol(b)) => Str(format_str!("{a}{b}")),
         (Symbol(a), Str(b)) => Str(format_str!("{a}{b}")),
+        (Bytes(a), Bytes(b)) => Bytes(a + b),
         (Content(a), Content(b)) => Content(a + b),
         (Content(a), Symbol(b)) => Content(a + item!(text)(b.get().into())),
         (Content(a), Str(b)) => Content(a + item!(text)(b.into())),
@@ -46,6 +47,7 @@ pub fn pos(value: Value) -> StrResult<Value> {
         Ratio(v) => Ratio(v),
         Relative(v) => Relative(v),
         Fraction(v) => Fraction(v),
+        Duration(v) => Duration(v),
         v => mismatch!("cannot apply '+' to {}", v),
     })
 }
@@ -60,6 +62,7 @@ pub fn neg(value: Value) -> StrResult<Value> {
         Ratio(v) => Ratio(-v),
         Relative(v) => Relative(-v),
         Fraction(v) => Fraction(-v),
+        Duration(v) => Duration(v.checked_neg()?),
         v => mismatch!("cannot apply '-' to {}", v),
     })
 }
@@ -91,10 +94,15 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
 
         (Fraction(a), Fraction(b)) => Fraction(a + b),
 
+        (Datetime(a), Duration(b)) => Datetime(a.checked_add(b)?),
+        (Duration(a), Datetime(b)) => Datetime(b.checked_add(a)?),
+        (Duration(a), Duration(b)) => Duration(a.checked_add(b)?),
+
         (Symbol(a), Symbol(b)) => Str(format_str!("{a}{b}")),
         (Str(a), Str(b)) => Str(a + b),
         (Str(a), Symbol(b)) => Str(format_str!("{a}{b}")),
         (Symbol(a), Str(b)) => Str(format_str!("{a}{b}")),
+        (Bytes(a), Bytes(b)) => Bytes(a + b),
         (Content(a), Content(b)) => Content(a + b),
         (Content(a), Symbol(b)) => Content(a + item!(text)(b.get().into())),
         (Content(a), Str(b)) => Content(a + item!(text)(b.into())),
@@ -157,6 +165,10 @@ pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
 
         (Fraction(a), Fraction(b)) => Fraction(a - b),
 
+        (Datetime(a), Duration(b)) => Datetime(a.checked_add(b.checked_neg()?)?),
+        (Datetime(a), Datetime(b)) => Duration(a.difference(b)?),
+        (Duration(a), Duration(b)) => Duration(a.checked_sub(b)?),
+
         (a, b) => mismatch!("cannot subtract {1} from {0}", a, b),
     })
 }
@@ -194,6 +206,9 @@ pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Fraction(a), Float(b)) => Fraction(a * b),
         (Int(a), Fraction(b)) => Fraction(a as f64 * b),
 
+        (Duration(a), Int(b)) => Duration(a.checked_mul(b)?),
+        (Int(a), Duration(b)) => Duration(b.checked_mul(a)?),
+
         (Str(a), Int(b)) => Str(a.repeat(b)?),
         (Int(a), Str(b)) => Str(b.repeat(a)?),
         (Array(a), Int(b)) => Array(a.repeat(b)?),
@@ -241,6 +256,8 @@ pub fn div(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Fraction(a), Float(b)) => Fraction(a / b),
         (Fraction(a), Fraction(b)) => Float(a / b),
 
+        (Duration(a), Duration(b)) => Float(a.seconds() / b.seconds()),
+
         (a, b) => mismatch!("cannot divide {} by {}", a, b),
     })
 }
@@ -255,6 +272,7 @@ fn is_zero(v: &Value) -> bool {
         Ratio(v) => v.is_zero(),
         Relative(v) => v.is_zero(),
         Fraction(v) => v.is_zero(),
+        Duration(v) => v.whole_seconds() == 0,
         _ => false,
     }
 }
@@ -339,7 +357,10 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (Color(a), Color(b)) => a == b,
         (Symbol(a), Symbol(b)) => a == b,
         (Str(a), Str(b)) => a == b,
+        (Bytes(a), Bytes(b)) => a == b,
         (Label(a), Label(b)) => a == b,
+        (Datetime(a), Datetime(b)) => a == b,
+        (Duration(a), Duration(b)) => a == b,
         (Content(a), Content(b)) => a == b,
         (Array(a), Array(b)) => a == b,
         (Dict(a), Dict(b)) => a == b,
@@ -372,6 +393,8 @@ pub fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
         (Relative(a), Relative(b)) => a.partial_cmp(b),
         (Fraction(a), Fraction(b)) => a.partial_cmp(b),
         (Str(a), Str(b)) => a.partial_cmp(b),
+        (Datetime(a), Datetime(b)) => a.partial_cmp(b),
+        (Duration(a), Duration(b)) => a.partial_cmp(b),
 
         // Some technically different things should be comparable.
         (&Int(a), &Float(b)) => (a as f64).partial_cmp(&b),
@@ -0,0 +1,177 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+use comemo::Tracked;
+use ecow::{eco_format, EcoString};
+use serde::Deserialize;
+
+use crate::diag::StrResult;
+use crate::syntax::is_ident;
+use crate::util::PathExt;
+use crate::World;
+
+/// The file name of a package's manifest.
+pub const MANIFEST: &str = "typst.toml";
+
+/// Identifies a versioned package, e.g. `@local/letter:0.2.0`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PackageSpec {
+    /// The namespace the package lives in.
+    pub namespace: EcoString,
+    /// The name of the package within its namespace.
+    pub name: EcoString,
+    /// The package's version.
+    pub version: PackageVersion,
+}
+
+impl PackageSpec {
+    /// Resolve the path of the package's entrypoint by reading its manifest.
+    pub fn entrypoint(&self, world: Tracked<dyn World>) -> StrResult<PathBuf> {
+        let dir = world.package(self)?;
+        let data = world.file(&dir.join(MANIFEST))?;
+        let manifest = PackageManifest::parse(&data)?;
+        manifest.validate(self)?;
+        Ok(dir.join(manifest.package.entrypoint).normalize())
+    }
+}
+
+impl FromStr for PackageSpec {
+    type Err = EcoString;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(rest) = s.strip_prefix('@') else {
+            return Err("package specification must start with `@`".into());
+        };
+
+        let Some((namespace, rest)) = rest.split_once('/') else {
+            return Err("package specification is missing name".into());
+        };
+
+        if namespace.is_empty() {
+            Err("package specification is missing namespace")?;
+        } else if !is_ident(namespace) {
+            Err(eco_format!("`{namespace}` is not a valid package namespace"))?;
+        }
+
+        let Some((name, version)) = rest.split_once(':') else {
+            return Err("package specification is missing version".into());
+        };
+
+        if name.is_empty() {
+            Err("package specification is missing name")?;
+        } else if !is_ident(name) {
+            Err(eco_format!("`{name}` is not a valid package name"))?;
+        }
+
+        Ok(Self {
+            namespace: namespace.into(),
+            name: name.into(),
+            version: version.parse()?,
+        })
+    }
+}
+
+impl Display for PackageSpec {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "@{}/{}:{}", self.namespace, self.name, self.version)
+    }
+}
+
+/// A package's version, consisting of major, minor and patch components.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PackageVersion {
+    /// The major version.
+    pub major: u32,
+    /// The minor version.
+    pub minor: u32,
+    /// The patch version.
+    pub patch: u32,
+}
+
+impl FromStr for PackageVersion {
+    type Err = EcoString;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('.');
+        let mut next = |kind: &str| {
+            let Some(part) = parts.next().filter(|part| !part.is_empty()) else {
+                return Err(eco_format!("version number is missing {kind} version"));
+            };
+            part.parse::<u32>()
+                .map_err(|_| eco_format!("`{part}` is not a valid {kind} version"))
+        };
+
+        let major = next("major")?;
+        let minor = next("minor")?;
+        let patch = next("patch")?;
+        if let Some(rest) = parts.next() {
+            Err(eco_format!("version number has unexpected fourth component: `{rest}`"))?;
+        }
+
+        Ok(Self { major, minor, patch })
+    }
+}
+
+impl Display for PackageVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A parsed package manifest.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct PackageManifest {
+    /// Details about the package itself.
+    pub package: PackageInfo,
+}
+
+/// The `[package]` section of a package manifest.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct PackageInfo {
+    /// The name of the package within its namespace.
+    pub name: String,
+    /// The package's version.
+    pub version: String,
+    /// The path of the main source file relative to the package's root.
+    pub entrypoint: String,
+}
+
+impl PackageManifest {
+    /// Parse a manifest from the contents of a `typst.toml` file.
+    pub fn parse(data: &[u8]) -> StrResult<Self> {
+        let text = std::str::from_utf8(data)
+            .map_err(|_| "package manifest is not valid utf-8")?;
+        toml::from_str(text).map_err(|err| {
+            eco_format!("package manifest is malformed ({})", err.message())
+        })
+    }
+
+    /// Ensure that the manifest describes the package with the given spec and
+    /// that its entrypoint lies within the package.
+    pub fn validate(&self, spec: &PackageSpec) -> StrResult<()> {
+        if self.package.name != spec.name.as_str() {
+            Err(eco_format!(
+                "package manifest contains mismatched name `{}`",
+                self.package.name
+            ))?;
+        }
+
+        let version: PackageVersion = self.package.version.parse()?;
+        if version != spec.version {
+            Err(eco_format!("package manifest contains mismatched version {version}"))?;
+        }
+
+        let entrypoint = Path::new(&self.package.entrypoint);
+        if entrypoint.components().any(|component| {
+            !matches!(component, Component::Normal(_) | Component::CurDir)
+        }) {
+            Err(eco_format!(
+                "package manifest contains entrypoint outside of the package: `{}`",
+                self.package.entrypoint
+            ))?;
+        }
+
+        Ok(())
+    }
+}
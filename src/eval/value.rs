@@ -8,8 +8,8 @@ use ecow::eco_format;
 use siphasher::sip128::{Hasher128, SipHasher};
 
 use super::{
-    cast_to_value, format_str, ops, Args, Array, Cast, CastInfo, Content, Dict, Func,
-    Label, Module, Str, Symbol,
+    cast_to_value, format_str, ops, Args, Array, Bytes, Cast, CastInfo, Content,
+    Datetime, Dict, Duration, Func, Label, Module, Str, Symbol,
 };
 use crate::diag::StrResult;
 use crate::geom::{Abs, Angle, Color, Em, Fr, Length, Ratio, Rel};
@@ -45,8 +45,14 @@ pub enum Value {
     Symbol(Symbol),
     /// A string: `"string"`.
     Str(Str),
+    /// Raw bytes: `read("data.bin", encoding: none)`.
+    Bytes(Bytes),
     /// A label: `<intro>`.
     Label(Label),
+    /// A date, a time or both: `datetime(year: 2023, month: 5, day: 1)`.
+    Datetime(Datetime),
+    /// A span of time: `duration(days: 3)`.
+    Duration(Duration),
     /// A content value: `[*Hi* there]`.
     Content(Content),
     // Content styles.
@@ -102,7 +108,10 @@ impl Value {
             Self::Color(_) => Color::TYPE_NAME,
             Self::Symbol(_) => Symbol::TYPE_NAME,
             Self::Str(_) => Str::TYPE_NAME,
+            Self::Bytes(_) => Bytes::TYPE_NAME,
             Self::Label(_) => Label::TYPE_NAME,
+            Self::Datetime(_) => Datetime::TYPE_NAME,
+            Self::Duration(_) => Duration::TYPE_NAME,
             Self::Content(_) => Content::TYPE_NAME,
             Self::Styles(_) => Styles::TYPE_NAME,
             Self::Array(_) => Array::TYPE_NAME,
@@ -126,6 +135,7 @@ impl Value {
             Self::Dict(dict) => dict.at(&field).cloned(),
             Self::Content(content) => content.at(&field),
             Self::Module(module) => module.get(&field).cloned(),
+            Self::Func(func) => func.get(&field).cloned(),
             v => Err(eco_format!("cannot access fields on type {}", v.type_name())),
         }
     }
@@ -190,7 +200,10 @@ impl Debug for Value {
             Self::Color(v) => Debug::fmt(v, f),
             Self::Symbol(v) => Debug::fmt(v, f),
             Self::Str(v) => Debug::fmt(v, f),
+            Self::Bytes(v) => Debug::fmt(v, f),
             Self::Label(v) => Debug::fmt(v, f),
+            Self::Datetime(v) => Debug::fmt(v, f),
+            Self::Duration(v) => Debug::fmt(v, f),
             Self::Content(v) => Debug::fmt(v, f),
             Self::Styles(v) => Debug::fmt(v, f),
             Self::Array(v) => Debug::fmt(v, f),
@@ -232,7 +245,10 @@ impl Hash for Value {
             Self::Color(v) => v.hash(state),
             Self::Symbol(v) => v.hash(state),
             Self::Str(v) => v.hash(state),
+            Self::Bytes(v) => v.hash(state),
             Self::Label(v) => v.hash(state),
+            Self::Datetime(v) => v.hash(state),
+            Self::Duration(v) => v.hash(state),
             Self::Content(v) => v.hash(state),
             Self::Styles(v) => v.hash(state),
             Self::Array(v) => v.hash(state),
@@ -399,7 +415,10 @@ primitive! {
     Str,
     Symbol(symbol) => symbol.get().into()
 }
+primitive! { Bytes: "bytes", Bytes }
 primitive! { Label: "label", Label }
+primitive! { Datetime: "datetime", Datetime }
+primitive! { Duration: "duration", Duration }
 primitive! { Content: "content",
     Content,
     None => Content::empty(),
@@ -449,5 +468,12 @@ mod tests {
         test(dict![], "(:)");
         test(dict!["one" => 1], "(one: 1)");
         test(dict!["two" => false, "one" => 1], "(one: 1, two: false)");
+
+        // Time.
+        test(
+            Datetime::from_ymd(2023, 5, 1).unwrap(),
+            "datetime(year: 2023, month: 5, day: 1)",
+        );
+        test(Duration::new(0, 1, 0, 0, 30).unwrap(), "duration(days: 1, seconds: 30)");
     }
 }
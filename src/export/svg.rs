@@ -0,0 +1,499 @@
+//! Exporting into SVG documents.
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use ttf_parser::{GlyphId, OutlineBuilder};
+
+use crate::doc::{Destination, Frame, FrameItem, GroupItem, Meta, TextItem};
+use crate::font::Font;
+use crate::geom::{Abs, Geometry, Paint, PathItem, Shape, Size, Stroke, Transform};
+use crate::image::{Image, ImageFormat, RasterFormat, VectorFormat};
+
+/// Export a frame into an SVG document.
+///
+/// Returns the standalone SVG document as a string. Links to URLs are exported
+/// as `<a>` elements, links to positions in the document are not, since they
+/// would point out of the single-page document.
+pub fn svg(frame: &Frame) -> String {
+    svg_with_prefix(frame, "")
+}
+
+/// Export a frame into an SVG document whose ids all start with the given
+/// prefix.
+///
+/// This way, multiple documents can be inlined into the same HTML page without
+/// their glyph and clip path definitions clashing.
+pub fn svg_with_prefix(frame: &Frame, prefix: &str) -> String {
+    let mut renderer = SvgRenderer { prefix: prefix.into(), ..Default::default() };
+    renderer.render_frame(frame);
+    renderer.finish(frame.size())
+}
+
+/// Renders frames into an SVG document.
+#[derive(Default)]
+struct SvgRenderer {
+    /// The prefix for all ids in the document.
+    prefix: String,
+    /// The visible elements.
+    body: String,
+    /// Glyph outlines and clip paths referenced by the body.
+    defs: String,
+    /// Maps from glyphs to the ids of their outline definitions.
+    glyphs: HashMap<(Font, u16), Option<usize>>,
+    /// The number of clip paths defined so far.
+    clips: usize,
+}
+
+impl SvgRenderer {
+    /// Write the SVG document.
+    fn finish(self, size: Size) -> String {
+        let w = size.x.to_f32();
+        let h = size.y.to_f32();
+        let mut svg = String::new();
+        write!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+             width=\"{w}pt\" height=\"{h}pt\" viewBox=\"0 0 {w} {h}\">"
+        )
+        .unwrap();
+        if !self.defs.is_empty() {
+            write!(svg, "<defs>{}</defs>", self.defs).unwrap();
+        }
+        svg.push_str(&self.body);
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Render a frame into the body.
+    ///
+    /// Links are rendered after all other items so that they end up on top
+    /// and remain clickable.
+    fn render_frame(&mut self, frame: &Frame) {
+        let (links, items): (Vec<_>, Vec<_>) = frame
+            .items()
+            .partition(|(_, item)| matches!(item, FrameItem::Meta(Meta::Link(_), _)));
+
+        for (pos, item) in items.into_iter().chain(links) {
+            let x = pos.x.to_f32();
+            let y = pos.y.to_f32();
+            write!(self.body, "<g transform=\"translate({x} {y})\">").unwrap();
+
+            match item {
+                FrameItem::Group(group) => self.render_group(group),
+                FrameItem::Text(text) => self.render_text(text),
+                FrameItem::Shape(shape, _) => self.render_shape(shape),
+                FrameItem::Image(image, size, _) => self.render_image(image, *size),
+                FrameItem::Meta(meta, size) => match meta {
+                    Meta::Link(dest) => self.render_link(dest, *size),
+                    Meta::Elem(_) => {}
+                    Meta::Hide => {}
+                },
+            }
+
+            self.body.push_str("</g>");
+        }
+    }
+
+    /// Render a group frame with optional transform and clipping.
+    fn render_group(&mut self, group: &GroupItem) {
+        self.body.push_str("<g");
+
+        if !group.transform.is_identity() {
+            let Transform { sx, ky, kx, sy, tx, ty } = group.transform;
+            write!(
+                self.body,
+                " transform=\"matrix({} {} {} {} {} {})\"",
+                sx.get() as f32,
+                ky.get() as f32,
+                kx.get() as f32,
+                sy.get() as f32,
+                tx.to_f32(),
+                ty.to_f32(),
+            )
+            .unwrap();
+        }
+
+        if group.clips {
+            let size = group.frame.size();
+            let prefix = &self.prefix;
+            let id = self.clips;
+            self.clips += 1;
+            write!(
+                self.defs,
+                "<clipPath id=\"{prefix}c{id}\">\
+                 <rect width=\"{}\" height=\"{}\"/></clipPath>",
+                size.x.to_f32(),
+                size.y.to_f32(),
+            )
+            .unwrap();
+            write!(self.body, " clip-path=\"url(#{prefix}c{id})\"").unwrap();
+        }
+
+        self.body.push('>');
+        self.render_frame(&group.frame);
+        self.body.push_str("</g>");
+    }
+
+    /// Render a text run.
+    fn render_text(&mut self, text: &TextItem) {
+        write!(self.body, "<g{}>", FillAttrs(Some(text.fill))).unwrap();
+
+        let size = text.size.to_f32();
+        let scale = size / text.font.units_per_em() as f32;
+        let mut x = 0.0;
+        for glyph in &text.glyphs {
+            let offset = x + glyph.x_offset.at(text.size).to_f32();
+            let id = GlyphId(glyph.id);
+
+            if let Some(def) = self.glyph_def(&text.font, id) {
+                // Flip vertically because font design coordinate system is
+                // Y-up.
+                write!(
+                    self.body,
+                    "<use xlink:href=\"#{}g{def}\" \
+                     transform=\"translate({offset} 0) scale({scale} -{scale})\"/>",
+                    self.prefix,
+                )
+                .unwrap();
+            } else {
+                self.render_bitmap_glyph(text, id, offset);
+            }
+
+            x += glyph.x_advance.at(text.size).to_f32();
+        }
+
+        self.body.push_str("</g>");
+    }
+
+    /// Retrieve or create the id of the outline definition for a glyph.
+    fn glyph_def(&mut self, font: &Font, id: GlyphId) -> Option<usize> {
+        let next = self.glyphs.len();
+        let prefix = &self.prefix;
+        let defs = &mut self.defs;
+        *self.glyphs.entry((font.clone(), id.0)).or_insert_with(|| {
+            let mut builder = SvgPathBuilder::default();
+            font.ttf().outline_glyph(id, &mut builder)?;
+            write!(defs, "<path id=\"{prefix}g{next}\" d=\"{}\"/>", builder.0).unwrap();
+            Some(next)
+        })
+    }
+
+    /// Render a bitmap glyph, which is used by color emoji fonts.
+    fn render_bitmap_glyph(&mut self, text: &TextItem, id: GlyphId, offset: f32) {
+        let size = text.size.to_f32();
+        let Some(raster) = text.font.ttf().glyph_raster_image(id, size as u16) else {
+            return;
+        };
+        let Ok(image) = Image::new(raster.data.into(), raster.format.into()) else {
+            return;
+        };
+
+        let h = text.size;
+        let w = (image.width() as f64 / image.height() as f64) * h;
+        let dx = (raster.x as f32) / (image.width() as f32) * size;
+        let dy = (raster.y as f32) / (image.height() as f32) * size;
+        write!(self.body, "<g transform=\"translate({} {})\">", offset + dx, -size - dy)
+            .unwrap();
+        self.render_image(&image, Size::new(w, h));
+        self.body.push_str("</g>");
+    }
+
+    /// Render a geometrical shape.
+    fn render_shape(&mut self, shape: &Shape) {
+        let attrs = ShapeAttrs(shape.fill, shape.stroke);
+        match shape.geometry {
+            Geometry::Line(target) => {
+                write!(
+                    self.body,
+                    "<path d=\"M0 0L{} {}\"{attrs}/>",
+                    target.x.to_f32(),
+                    target.y.to_f32(),
+                )
+                .unwrap();
+            }
+            Geometry::Rect(size) => {
+                write!(
+                    self.body,
+                    "<rect width=\"{}\" height=\"{}\"{attrs}/>",
+                    size.x.to_f32(),
+                    size.y.to_f32(),
+                )
+                .unwrap();
+            }
+            Geometry::Path(ref path) => {
+                let mut builder = SvgPathBuilder::default();
+                for item in &path.0 {
+                    match item {
+                        PathItem::MoveTo(p) => {
+                            builder.move_to(p.x.to_f32(), p.y.to_f32());
+                        }
+                        PathItem::LineTo(p) => {
+                            builder.line_to(p.x.to_f32(), p.y.to_f32());
+                        }
+                        PathItem::CubicTo(p1, p2, p3) => {
+                            builder.curve_to(
+                                p1.x.to_f32(),
+                                p1.y.to_f32(),
+                                p2.x.to_f32(),
+                                p2.y.to_f32(),
+                                p3.x.to_f32(),
+                                p3.y.to_f32(),
+                            );
+                        }
+                        PathItem::ClosePath => {
+                            builder.close();
+                        }
+                    }
+                }
+                write!(self.body, "<path d=\"{}\"{attrs}/>", builder.0).unwrap();
+            }
+        }
+    }
+
+    /// Render a raster or SVG image, embedded as a data URL.
+    fn render_image(&mut self, image: &Image, size: Size) {
+        let mime = match image.format() {
+            ImageFormat::Raster(RasterFormat::Png) => "image/png",
+            ImageFormat::Raster(RasterFormat::Jpg) => "image/jpeg",
+            ImageFormat::Raster(RasterFormat::Gif) => "image/gif",
+            ImageFormat::Vector(VectorFormat::Svg) => "image/svg+xml",
+        };
+
+        write!(
+            self.body,
+            "<image width=\"{}\" height=\"{}\" preserveAspectRatio=\"none\" \
+             xlink:href=\"data:{mime};base64,{}\"/>",
+            size.x.to_f32(),
+            size.y.to_f32(),
+            base64(image.data()),
+        )
+        .unwrap();
+    }
+
+    /// Render a link as a transparent, clickable area.
+    fn render_link(&mut self, dest: &Destination, size: Size) {
+        let Destination::Url(url) = dest else { return };
+        write!(
+            self.body,
+            "<a xlink:href=\"{}\"><rect width=\"{}\" height=\"{}\" \
+             fill=\"transparent\"/></a>",
+            Escaped(url.as_str()),
+            size.x.to_f32(),
+            size.y.to_f32(),
+        )
+        .unwrap();
+    }
+}
+
+/// Fill attributes of an SVG element.
+struct FillAttrs(Option<Paint>);
+
+impl fmt::Display for FillAttrs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(Paint::Solid(color)) => {
+                let c = color.to_rgba();
+                write!(f, " fill=\"#{:02x}{:02x}{:02x}\"", c.r, c.g, c.b)?;
+                if c.a != 255 {
+                    write!(f, " fill-opacity=\"{}\"", c.a as f32 / 255.0)?;
+                }
+                Ok(())
+            }
+            None => f.write_str(" fill=\"none\""),
+        }
+    }
+}
+
+/// Fill and stroke attributes of an SVG shape.
+struct ShapeAttrs(Option<Paint>, Option<Stroke>);
+
+impl fmt::Display for ShapeAttrs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", FillAttrs(self.0))?;
+        if let Some(Stroke { paint: Paint::Solid(color), thickness }) = self.1 {
+            let c = color.to_rgba();
+            write!(f, " stroke=\"#{:02x}{:02x}{:02x}\"", c.r, c.g, c.b)?;
+            if c.a != 255 {
+                write!(f, " stroke-opacity=\"{}\"", c.a as f32 / 255.0)?;
+            }
+            write!(f, " stroke-width=\"{}\"", thickness.to_f32())?;
+        }
+        Ok(())
+    }
+}
+
+/// Escapes a string for use in an XML attribute.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                _ => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds SVG path data from glyph outlines and Typst paths.
+#[derive(Default)]
+struct SvgPathBuilder(String);
+
+impl OutlineBuilder for SvgPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        write!(self.0, "M{x} {y}").unwrap();
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        write!(self.0, "L{x} {y}").unwrap();
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        write!(self.0, "Q{x1} {y1} {x} {y}").unwrap();
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        write!(self.0, "C{x1} {y1} {x2} {y2} {x} {y}").unwrap();
+    }
+
+    fn close(&mut self) {
+        self.0.push('Z');
+    }
+}
+
+/// Encode bytes with the standard base64 alphabet and padding.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Additional methods for [`Abs`].
+trait AbsExt {
+    /// Convert to a number of points as f32.
+    fn to_f32(self) -> f32;
+}
+
+impl AbsExt for Abs {
+    fn to_f32(self) -> f32 {
+        self.to_pt() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::doc::{Glyph, Lang};
+    use crate::geom::{Color, Em, Point};
+    use crate::syntax::Span;
+
+    #[test]
+    fn test_svg_smoke() {
+        let font = Font::new(
+            std::fs::read("assets/fonts/LinLibertine_R.ttf").unwrap().into(),
+            0,
+        )
+        .unwrap();
+        let image = Image::new(
+            std::fs::read("assets/files/rhino.png").unwrap().into(),
+            ImageFormat::Raster(RasterFormat::Png),
+        )
+        .unwrap();
+
+        let text = TextItem {
+            font: font.clone(),
+            size: Abs::pt(11.0),
+            fill: Color::BLACK.into(),
+            lang: Lang::ENGLISH,
+            glyphs: "Hi & <bye>"
+                .chars()
+                .map(|c| Glyph {
+                    id: font.ttf().glyph_index(c).map_or(0, |id| id.0),
+                    x_advance: Em::new(0.5),
+                    x_offset: Em::zero(),
+                    c,
+                    span: Span::detached(),
+                    offset: 0,
+                })
+                .collect(),
+        };
+
+        let size = Size::new(Abs::pt(100.0), Abs::pt(50.0));
+        let mut frame = Frame::new(size);
+        for _ in 0..2 {
+            let mut inner = Frame::new(size);
+            inner.push(Point::zero(), FrameItem::Text(text.clone()));
+            let mut group = GroupItem::new(inner);
+            group.clips = true;
+            frame.push(Point::zero(), FrameItem::Group(group));
+        }
+        frame.push(Point::zero(), FrameItem::Image(image, size, Span::detached()));
+        frame.push(
+            Point::zero(),
+            FrameItem::Meta(
+                Meta::Link(Destination::Url("https://typst.app/?a&b".into())),
+                size,
+            ),
+        );
+
+        let svg = svg_with_prefix(&frame, "p0-");
+        let doc = roxmltree::Document::parse(&svg).unwrap();
+        let xlink = "http://www.w3.org/1999/xlink";
+
+        // All ids are prefixed and unique.
+        let mut ids = HashSet::new();
+        for id in doc.descendants().filter_map(|node| node.attribute("id")) {
+            assert!(id.starts_with("p0-"), "unprefixed id {id}");
+            assert!(ids.insert(id), "duplicate id {id}");
+        }
+        assert!(ids.contains("p0-g0"));
+        assert!(ids.contains("p0-c0") && ids.contains("p0-c1"));
+
+        // All references resolve to a defined id.
+        for node in doc.descendants() {
+            if let Some(href) = node.attribute((xlink, "href")) {
+                if let Some(id) = href.strip_prefix('#') {
+                    assert!(ids.contains(id), "dangling reference {href}");
+                }
+            }
+            if let Some(clip) = node.attribute("clip-path") {
+                let id = clip.strip_prefix("url(#").unwrap().strip_suffix(')').unwrap();
+                assert!(ids.contains(id), "dangling clip path {clip}");
+            }
+        }
+
+        // Links and images are included.
+        let has = |tag: &str, prefix: &str| {
+            doc.descendants().any(|node| {
+                node.has_tag_name(tag)
+                    && node
+                        .attribute((xlink, "href"))
+                        .map_or(false, |href| href.starts_with(prefix))
+            })
+        };
+        assert!(has("a", "https://typst.app/?a&b"));
+        assert!(has("image", "data:image/png;base64,"));
+    }
+}
@@ -53,6 +53,13 @@ impl FontBook {
         })
     }
 
+    /// Whether the book knows any font of the given `family`.
+    ///
+    /// The `family` should be all lowercase.
+    pub fn contains_family(&self, family: &str) -> bool {
+        self.families.contains_key(family)
+    }
+
     /// Try to find and load a font from the given `family` that matches
     /// the given `variant` as closely as possible.
     ///
@@ -20,6 +20,14 @@ impl<T> Smart<T> {
         matches!(self, Self::Custom(_))
     }
 
+    /// Returns the contained custom value, if any.
+    pub fn as_custom(self) -> Option<T> {
+        match self {
+            Self::Auto => None,
+            Self::Custom(x) => Some(x),
+        }
+    }
+
     /// Map the contained custom value with `f`.
     pub fn map<F, U>(self, f: F) -> Smart<U>
     where
@@ -4,9 +4,9 @@ use comemo::Track;
 use ecow::EcoString;
 
 use crate::doc::Frame;
-use crate::eval::{eval, Module, Route, Tracer, Value};
+use crate::eval::{eval, Module, PackageSpec, Route, Tracer, Value};
 use crate::model::{Introspector, Label};
-use crate::syntax::{ast, LinkedNode, Source, SyntaxKind};
+use crate::syntax::{ast, LinkedNode, Source, SourceId, SyntaxKind};
 use crate::util::PathExt;
 use crate::World;
 
@@ -66,18 +66,30 @@ pub fn analyze_import(
     source: &Source,
     path: &str,
 ) -> Option<Module> {
-    let full: PathBuf = if let Some(path) = path.strip_prefix('/') {
-        world.root().join(path).normalize()
+    let id = resolve_import(world, source, path)?;
+    let route = Route::default();
+    let mut tracer = Tracer::default();
+    let source = world.source(id);
+    eval(world.track(), route.track(), tracer.track_mut(), source).ok()
+}
+
+/// Try to find the file an import or include path refers to.
+pub fn resolve_import(
+    world: &(dyn World + 'static),
+    source: &Source,
+    path: &str,
+) -> Option<SourceId> {
+    let full: PathBuf = if path.starts_with('@') {
+        let spec = path.parse::<PackageSpec>().ok()?;
+        spec.entrypoint(world.track()).ok()?
+    } else if let Some(path) = path.strip_prefix('/') {
+        world.root_of(source.id()).join(path).normalize()
     } else if let Some(dir) = source.path().parent() {
         dir.join(path).normalize()
     } else {
         path.into()
     };
-    let route = Route::default();
-    let mut tracer = Tracer::default();
-    let id = world.resolve(&full).ok()?;
-    let source = world.source(id);
-    eval(world.track(), route.track(), tracer.track_mut(), source).ok()
+    world.resolve(&full).ok()
 }
 
 /// Find all labels and details for them.
@@ -386,6 +386,13 @@ fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
                 ctx.value_completion(Some(name.clone()), value, true, None);
             }
         }
+        Value::Func(func) => {
+            if let Some(info) = func.info() {
+                for (name, value) in info.scope.iter() {
+                    ctx.value_completion(Some(name.clone()), value, true, None);
+                }
+            }
+        }
         _ => {}
     }
 }
@@ -432,7 +439,7 @@ fn complete_imports(ctx: &mut CompletionContext) -> bool {
 /// Add completions for all exports of a module.
 fn import_completions(
     ctx: &mut CompletionContext,
-    existing: &[ast::Ident],
+    existing: &[ast::ImportItem],
     value: &Value,
 ) {
     let module = match value {
@@ -449,7 +456,7 @@ fn import_completions(
     }
 
     for (name, value) in module.scope().iter() {
-        if existing.iter().all(|ident| ident.as_str() != name) {
+        if existing.iter().all(|item| item.original_name().as_str() != name) {
             ctx.value_completion(Some(name.clone()), value, false, None);
         }
     }
@@ -1096,7 +1103,9 @@ impl<'a> CompletionContext<'a> {
             let mut sibling = Some(node.clone());
             while let Some(node) = &sibling {
                 if let Some(v) = node.cast::<ast::LetBinding>() {
-                    defined.insert(v.binding().take());
+                    for ident in v.pattern().idents() {
+                        defined.insert(ident.take());
+                    }
                 }
                 sibling = node.prev_sibling();
             }
@@ -1108,7 +1117,9 @@ impl<'a> CompletionContext<'a> {
                         if let Some(key) = pattern.key() {
                             defined.insert(key.take());
                         }
-                        defined.insert(pattern.value().take());
+                        for ident in pattern.value().idents() {
+                            defined.insert(ident.take());
+                        }
                     }
                 }
 
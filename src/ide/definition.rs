@@ -0,0 +1,539 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use ecow::EcoString;
+
+use super::analyze::resolve_import;
+use crate::eval::PackageSpec;
+use crate::syntax::ast::{self, AstNode};
+use crate::syntax::{LinkedNode, Source, SourceId, SyntaxKind};
+use crate::World;
+
+/// How many imports to follow in a row before giving up, e.g. in case of
+/// cyclic imports.
+const MAX_IMPORT_DEPTH: usize = 16;
+
+/// Where an item is defined.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Definition {
+    /// A binding, with the byte range of its identifier in the file.
+    Binding(SourceId, Range<usize>),
+    /// A whole file, e.g. an imported module.
+    File(SourceId),
+}
+
+impl Definition {
+    /// A definition at an identifier in a source file.
+    fn ident(source: &Source, ident: &ast::Ident) -> Self {
+        Self::Binding(source.id(), source.range(ident.span()))
+    }
+
+    /// The file that contains the definition.
+    pub fn source(&self) -> SourceId {
+        match self {
+            Self::Binding(source, _) | Self::File(source) => *source,
+        }
+    }
+
+    /// The byte range of the definition in its file.
+    ///
+    /// Empty and at the start of the file for a whole file.
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Binding(_, range) => range.clone(),
+            Self::File(_) => 0..0,
+        }
+    }
+}
+
+/// Find the definition of the item under the cursor.
+///
+/// Identifiers resolve to their `let` binding, closure parameter or loop
+/// variable. Imported identifiers resolve to their binding in the imported
+/// file and paths in imports and includes resolve to the file itself.
+pub fn definition(
+    world: &(dyn World + 'static),
+    source: &Source,
+    cursor: usize,
+) -> Option<Definition> {
+    let leaf = LinkedNode::new(source.root()).leaf_at(cursor)?;
+    resolve(world, source, &leaf, 0)
+}
+
+/// Find all references to the item under the cursor.
+///
+/// For identifiers, these are all uses that resolve to the same definition,
+/// including the definition itself. For labels and references, these are all
+/// labels with the same name and references to them. Searches the main file
+/// and all files it imports or includes.
+pub fn references(
+    world: &(dyn World + 'static),
+    source: &Source,
+    cursor: usize,
+) -> Vec<(SourceId, Range<usize>)> {
+    let Some(leaf) = LinkedNode::new(source.root()).leaf_at(cursor) else {
+        return vec![];
+    };
+
+    let sources = reachable(world, source);
+    let mut found = vec![];
+
+    if let Some(label) = label_name(&leaf) {
+        for &id in &sources {
+            let source = world.source(id);
+            visit(&LinkedNode::new(source.root()), &mut |node| {
+                if label_name(node) == Some(label) {
+                    found.push((id, node.range()));
+                }
+            });
+        }
+        return found;
+    }
+
+    if !matches!(leaf.kind(), SyntaxKind::Ident | SyntaxKind::MathIdent) {
+        return found;
+    }
+
+    let Some(target) = resolve(world, source, &leaf, 0) else { return found };
+
+    // Only identifiers with one of these names can refer to the target. A
+    // renamed import binds the target under an additional name.
+    let mut names = HashSet::new();
+    names.insert(leaf.text().clone());
+    if let Definition::Binding(id, range) = &target {
+        if let Some(name) = world.source(*id).get(range.clone()) {
+            names.insert(name.into());
+        }
+    }
+
+    for &id in &sources {
+        let source = world.source(id);
+        visit(&LinkedNode::new(source.root()), &mut |node| {
+            let Some(renamed) = node.cast::<ast::RenamedImportItem>() else { return };
+            if !names.contains(renamed.original_name().get()) {
+                return;
+            }
+
+            let Some(original) = node.children().find(|child| child.is::<ast::Ident>())
+            else {
+                return;
+            };
+
+            if resolve(world, source, &original, 0).as_ref() == Some(&target) {
+                names.insert(renamed.new_name().take());
+            }
+        });
+    }
+
+    for &id in &sources {
+        let source = world.source(id);
+        visit(&LinkedNode::new(source.root()), &mut |node| {
+            if matches!(node.kind(), SyntaxKind::Ident | SyntaxKind::MathIdent)
+                && names.contains(node.text())
+                && resolve(world, source, node, 0).as_ref() == Some(&target)
+            {
+                found.push((id, node.range()));
+            }
+        });
+    }
+
+    found
+}
+
+/// Resolve the definition of a leaf.
+pub(super) fn resolve(
+    world: &(dyn World + 'static),
+    source: &Source,
+    leaf: &LinkedNode,
+    depth: usize,
+) -> Option<Definition> {
+    let parent = leaf.parent()?;
+    match leaf.kind() {
+        // A path in an import or include refers to the file.
+        SyntaxKind::Str
+            if matches!(
+                parent.kind(),
+                SyntaxKind::ModuleImport | SyntaxKind::ModuleInclude
+            ) =>
+        {
+            let path = leaf.cast::<ast::Str>()?.get();
+            resolve_import(world, source, &path).map(Definition::File)
+        }
+
+        SyntaxKind::Ident | SyntaxKind::MathIdent => {
+            match parent.kind() {
+                // An item in an import list refers to the binding in the
+                // imported file.
+                SyntaxKind::ImportItems => {
+                    let import = parent.parent()?.cast::<ast::ModuleImport>()?;
+                    let (id, _) = import_target(world, source, &import)?;
+                    return module_binding(world, id, leaf.text(), depth + 1);
+                }
+                SyntaxKind::RenamedImportItem => {
+                    let renamed = parent.cast::<ast::RenamedImportItem>()?;
+                    let import =
+                        parent.parent()?.parent()?.cast::<ast::ModuleImport>()?;
+                    let (id, _) = import_target(world, source, &import)?;
+                    let name = renamed.original_name();
+                    return module_binding(world, id, &name, depth + 1);
+                }
+
+                // The name of a named argument, dictionary pair or
+                // destructured field is not a variable.
+                SyntaxKind::Named if !is_variable(leaf) => return None,
+
+                // A field on a module refers to the binding in that module.
+                SyntaxKind::FieldAccess if leaf.index() > 0 => {
+                    let target = parent.children().next()?;
+                    if target.kind() != SyntaxKind::Ident {
+                        return None;
+                    }
+
+                    let Definition::File(id) = resolve(world, source, &target, depth)?
+                    else {
+                        return None;
+                    };
+
+                    return module_binding(world, id, leaf.text(), depth + 1);
+                }
+                _ => {}
+            }
+
+            if declares(leaf) {
+                return Some(Definition::Binding(source.id(), leaf.range()));
+            }
+
+            find_in_scope(world, source, leaf, leaf.text(), depth)
+        }
+
+        _ => None,
+    }
+}
+
+/// Whether an identifier refers to a variable in its scope rather than, for
+/// example, to a field or to the name of a named argument.
+pub(super) fn is_variable(leaf: &LinkedNode) -> bool {
+    let Some(parent) = leaf.parent() else { return false };
+    match parent.kind() {
+        SyntaxKind::ImportItems | SyntaxKind::RenamedImportItem => false,
+        SyntaxKind::FieldAccess => leaf.index() == 0,
+        SyntaxKind::Named => {
+            leaf.index() > 0
+                || !matches!(
+                    parent.parent_kind(),
+                    Some(SyntaxKind::Args | SyntaxKind::Dict | SyntaxKind::Destructuring)
+                )
+        }
+        _ => true,
+    }
+}
+
+/// Whether an identifier is itself the binding of a let binding, closure or
+/// for loop.
+fn declares(leaf: &LinkedNode) -> bool {
+    let mut node = leaf.parent();
+    while let Some(ancestor) = node {
+        if matches!(
+            ancestor.kind(),
+            SyntaxKind::LetBinding | SyntaxKind::Closure | SyntaxKind::ForLoop
+        ) {
+            return bindings(ancestor).iter().any(|ident| ident.span() == leaf.span());
+        }
+        node = ancestor.parent();
+    }
+    false
+}
+
+/// Search the scopes visible from a node for the binding of a name.
+pub(super) fn find_in_scope(
+    world: &(dyn World + 'static),
+    source: &Source,
+    leaf: &LinkedNode,
+    name: &str,
+    depth: usize,
+) -> Option<Definition> {
+    let mut node = leaf.clone();
+    loop {
+        let mut sibling = node.prev_sibling();
+        while let Some(prev) = sibling {
+            if let Some(definition) = find_in_sibling(world, source, &prev, name, depth) {
+                return Some(definition);
+            }
+            sibling = prev.prev_sibling();
+        }
+
+        let parent = node.parent()?.clone();
+        let visible = match parent.kind() {
+            // Loop variables are visible in the body, but not in the iterable.
+            SyntaxKind::ForLoop => node.prev_sibling_kind() != Some(SyntaxKind::In),
+            // Parameters and the closure's own name are visible in its body.
+            SyntaxKind::Closure => true,
+            _ => false,
+        };
+
+        if visible {
+            if let Some(ident) =
+                bindings(&parent).into_iter().find(|ident| ident.as_str() == name)
+            {
+                return Some(Definition::ident(source, &ident));
+            }
+        }
+
+        node = parent;
+    }
+}
+
+/// Find a binding of a name in a let binding or import preceding a use.
+fn find_in_sibling(
+    world: &(dyn World + 'static),
+    source: &Source,
+    node: &LinkedNode,
+    name: &str,
+    depth: usize,
+) -> Option<Definition> {
+    match node.cast::<ast::Expr>()? {
+        ast::Expr::Let(binding) => binding
+            .pattern()
+            .idents()
+            .into_iter()
+            .find(|ident| ident.as_str() == name)
+            .map(|ident| Definition::ident(source, &ident)),
+        ast::Expr::Import(import) => import_binding(world, source, &import, name, depth),
+        _ => None,
+    }
+}
+
+/// Find the binding of a name introduced by an import.
+fn import_binding(
+    world: &(dyn World + 'static),
+    source: &Source,
+    import: &ast::ModuleImport,
+    name: &str,
+    depth: usize,
+) -> Option<Definition> {
+    if depth >= MAX_IMPORT_DEPTH {
+        return None;
+    }
+
+    let (id, module) = import_target(world, source, import)?;
+    if let Some(new_name) = import.new_name() {
+        if new_name.as_str() == name {
+            return Some(Definition::File(id));
+        }
+    }
+
+    match import.imports() {
+        None if import.new_name().is_none() && module == name => {
+            Some(Definition::File(id))
+        }
+        None => None,
+        Some(ast::Imports::Wildcard) => module_binding(world, id, name, depth + 1),
+        Some(ast::Imports::Items(items)) => {
+            let item = items.iter().find(|item| item.bound_name().as_str() == name)?;
+            module_binding(world, id, &item.original_name(), depth + 1)
+        }
+    }
+}
+
+/// Find a top-level binding in a file.
+fn module_binding(
+    world: &(dyn World + 'static),
+    id: SourceId,
+    name: &str,
+    depth: usize,
+) -> Option<Definition> {
+    let source = world.source(id);
+    LinkedNode::new(source.root())
+        .children()
+        .rev()
+        .find_map(|child| find_in_sibling(world, source, &child, name, depth))
+}
+
+/// The file an import refers to and the name of the module it creates.
+fn import_target(
+    world: &(dyn World + 'static),
+    source: &Source,
+    import: &ast::ModuleImport,
+) -> Option<(SourceId, EcoString)> {
+    let ast::Expr::Str(path) = import.source() else { return None };
+    let path = path.get();
+    let id = resolve_import(world, source, &path)?;
+    let name = match path.parse::<PackageSpec>() {
+        Ok(spec) => spec.name,
+        Err(_) => world.source(id).path().file_stem()?.to_str()?.into(),
+    };
+    Some((id, name))
+}
+
+/// The identifiers bound by a let binding, closure or for loop.
+fn bindings(node: &LinkedNode) -> Vec<ast::Ident> {
+    match node.cast::<ast::Expr>() {
+        Some(ast::Expr::Let(binding)) => binding.pattern().idents(),
+        Some(ast::Expr::Closure(closure)) => closure
+            .name()
+            .into_iter()
+            .chain(closure.params().children().map(|param| match param {
+                ast::Param::Pos(ident) => ident,
+                ast::Param::Named(named) => named.name(),
+                ast::Param::Sink(ident) => ident,
+            }))
+            .collect(),
+        Some(ast::Expr::For(for_loop)) => {
+            let pattern = for_loop.pattern();
+            pattern.key().into_iter().chain(pattern.value().idents()).collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// All files reachable from the main file and the given file through imports
+/// and includes.
+pub(super) fn reachable(world: &(dyn World + 'static), source: &Source) -> Vec<SourceId> {
+    let mut seen = vec![];
+    let mut queue = vec![source.id(), world.main().id()];
+    while let Some(id) = queue.pop() {
+        if id.is_detached() || seen.contains(&id) {
+            continue;
+        }
+
+        seen.push(id);
+        let source = world.source(id);
+        visit(&LinkedNode::new(source.root()), &mut |node| {
+            let path = match node.cast::<ast::Expr>() {
+                Some(ast::Expr::Import(import)) => import.source(),
+                Some(ast::Expr::Include(include)) => include.source(),
+                _ => return,
+            };
+
+            if let ast::Expr::Str(path) = path {
+                queue.extend(resolve_import(world, source, &path.get()));
+            }
+        });
+    }
+    seen
+}
+
+/// The name of a label or of the label a reference points to.
+pub(super) fn label_name<'a>(node: &'a LinkedNode) -> Option<&'a str> {
+    let text = node.text();
+    match node.kind() {
+        SyntaxKind::Label => text.strip_prefix('<')?.strip_suffix('>'),
+        SyntaxKind::RefMarker => text.strip_prefix('@'),
+        _ => None,
+    }
+}
+
+/// Call a function for a node and all of its descendants.
+pub(super) fn visit<'a>(node: &LinkedNode<'a>, f: &mut impl FnMut(&LinkedNode<'a>)) {
+    f(node);
+    for child in node.children() {
+        visit(&child, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ide::tests::TestWorld;
+
+    const MAIN: SourceId = SourceId::from_u16(0);
+    const OTHER: SourceId = SourceId::from_u16(1);
+
+    const FILES: &[(&str, &str)] = &[
+        (
+            "/main.typ",
+            "#import \"other.typ\": f\n#import \"other.typ\"\n\
+             #let x = 1\n#let g(y) = x + y\n#g(f(x)) #other.f\n\
+             = Intro <intro>\n@intro",
+        ),
+        ("/other.typ", "#let f(x) = x\nSee @intro."),
+    ];
+
+    /// The byte range of the `nth` occurrence of `needle` in `text`.
+    #[track_caller]
+    fn find(text: &str, needle: &str, nth: usize) -> Range<usize> {
+        let start = text.match_indices(needle).nth(nth).unwrap().0;
+        start..start + needle.len()
+    }
+
+    #[test]
+    fn test_definition() {
+        let world = TestWorld::new(FILES);
+        let main = world.source(MAIN);
+        let text = main.text();
+        let other = world.source(OTHER).text();
+        let test = |range: Range<usize>| definition(&world, main, range.start + 1);
+
+        // Local bindings and closure parameters.
+        let x = Definition::Binding(MAIN, find(text, "x", 0));
+        assert_eq!(test(find(text, "x", 1)), Some(x.clone()));
+        assert_eq!(test(find(text, "x", 2)), Some(x));
+        let y = find(text, "y)", 0);
+        assert_eq!(
+            test(find(text, "y", 3)),
+            Some(Definition::Binding(MAIN, y.start..y.start + 1))
+        );
+        assert_eq!(
+            test(find(text, "g", 1)),
+            Some(Definition::Binding(MAIN, find(text, "g", 0)))
+        );
+
+        // Imported items and fields of modules refer to the imported binding.
+        let f = Definition::Binding(OTHER, find(other, "f", 0));
+        assert_eq!(test(find(text, "f", 0)), Some(f.clone()));
+        assert_eq!(test(find(text, "f", 1)), Some(f.clone()));
+        assert_eq!(test(find(text, "f", 2)), Some(f));
+
+        // Modules and import paths refer to the whole file.
+        assert_eq!(test(find(text, "other", 2)), Some(Definition::File(OTHER)));
+        assert_eq!(test(find(text, "\"other.typ\"", 0)), Some(Definition::File(OTHER)));
+
+        // Markup and unknown identifiers have no definition.
+        assert_eq!(test(find(text, "Intro", 0)), None);
+        assert_eq!(definition(&world, world.source(OTHER), 2), None);
+    }
+
+    #[test]
+    fn test_references() {
+        let world = TestWorld::new(FILES);
+        let main = world.source(MAIN);
+        let text = main.text();
+        let other = world.source(OTHER).text();
+        let test = |range: Range<usize>| {
+            let mut found = references(&world, main, range.start + 1);
+            found.sort_by_key(|(id, range)| (id.into_u16(), range.start));
+            found
+        };
+
+        // A local binding and its uses.
+        assert_eq!(
+            test(find(text, "x", 1)),
+            [
+                (MAIN, find(text, "x", 0)),
+                (MAIN, find(text, "x", 1)),
+                (MAIN, find(text, "x", 2))
+            ]
+        );
+
+        // An imported function and its uses across files.
+        assert_eq!(
+            test(find(text, "f", 1)),
+            [
+                (MAIN, find(text, "f", 0)),
+                (MAIN, find(text, "f", 1)),
+                (MAIN, find(text, "f", 2)),
+                (OTHER, find(other, "f", 0)),
+            ]
+        );
+
+        // A label and the references to it in both files.
+        assert_eq!(
+            test(find(text, "@intro", 0)),
+            [
+                (MAIN, find(text, "<intro>", 0)),
+                (MAIN, find(text, "@intro", 0)),
+                (OTHER, find(other, "@intro", 0)),
+            ]
+        );
+    }
+}
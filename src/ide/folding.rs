@@ -0,0 +1,111 @@
+use std::ops::Range;
+
+use crate::syntax::{ast, LinkedNode, Source, SyntaxKind};
+
+/// Find the foldable ranges in a source file.
+///
+/// These are the sections started by headings, code and content blocks as
+/// well as raw blocks. Only ranges that span multiple lines are included.
+pub fn folding(source: &Source) -> Vec<Fold> {
+    let root = LinkedNode::new(source.root());
+    let mut folds = vec![];
+
+    let headings: Vec<_> = root
+        .children()
+        .filter_map(|child| Some((child.cast::<ast::Heading>()?.level(), child.offset())))
+        .collect();
+
+    for (i, &(level, start)) in headings.iter().enumerate() {
+        let end = headings[i + 1..]
+            .iter()
+            .find(|&&(next, _)| next <= level)
+            .map_or(source.len_bytes(), |&(_, offset)| offset);
+
+        // Don't swallow the blank lines before the next heading.
+        let end = start + source.text()[start..end].trim_end().len();
+        folds.push(Fold { range: start..end, kind: FoldKind::Section });
+    }
+
+    blocks(&root, &mut folds);
+    folds.retain(|fold| {
+        source.byte_to_line(fold.range.start) != source.byte_to_line(fold.range.end)
+    });
+    folds
+}
+
+/// A foldable range in a source file.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fold {
+    /// The byte range that can be folded.
+    pub range: Range<usize>,
+    /// What kind of range this is.
+    pub kind: FoldKind,
+}
+
+/// What kind of range a fold is.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FoldKind {
+    /// A heading and the content up to the next heading of the same or a
+    /// higher level.
+    Section,
+    /// A code or content block.
+    Block,
+    /// A raw block.
+    Raw,
+}
+
+/// Collect the blocks in a node and its descendants.
+fn blocks(node: &LinkedNode, folds: &mut Vec<Fold>) {
+    let kind = match node.kind() {
+        SyntaxKind::CodeBlock | SyntaxKind::ContentBlock => Some(FoldKind::Block),
+        SyntaxKind::Raw => Some(FoldKind::Raw),
+        _ => None,
+    };
+
+    if let Some(kind) = kind {
+        folds.push(Fold { range: node.range(), kind });
+    }
+
+    for child in node.children() {
+        blocks(&child, folds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn test(text: &str, goal: &[(FoldKind, &str)]) {
+        let source = Source::detached(text);
+        let folds: Vec<_> = folding(&source)
+            .into_iter()
+            .map(|fold| (fold.kind, &text[fold.range]))
+            .collect();
+        assert_eq!(folds, goal);
+    }
+
+    #[test]
+    fn test_folding() {
+        use FoldKind::*;
+
+        // Sections end before the next heading of the same or a higher level.
+        test(
+            "= A\ntext\n== B\nmore\n\n= C\nend",
+            &[
+                (Section, "= A\ntext\n== B\nmore"),
+                (Section, "== B\nmore"),
+                (Section, "= C\nend"),
+            ],
+        );
+
+        // Blocks and raw blocks that span multiple lines.
+        test(
+            "#{\n  [a\n  b]\n}\n```\nraw\n```",
+            &[(Block, "{\n  [a\n  b]\n}"), (Block, "[a\n  b]"), (Raw, "```\nraw\n```")],
+        );
+
+        // Single-line ranges can't be folded.
+        test("= A #{1} #[b] `c`\n= B", &[]);
+    }
+}
@@ -48,6 +48,33 @@ pub enum Tag {
 }
 
 impl Tag {
+    /// The list of all tags, in the same order as they are defined.
+    ///
+    /// Can be used as the legend of an LSP semantic token provider.
+    pub const LIST: &'static [Tag] = &[
+        Self::Comment,
+        Self::Punctuation,
+        Self::Escape,
+        Self::Strong,
+        Self::Emph,
+        Self::Link,
+        Self::Raw,
+        Self::Label,
+        Self::Ref,
+        Self::Heading,
+        Self::ListMarker,
+        Self::ListTerm,
+        Self::MathDelimiter,
+        Self::MathOperator,
+        Self::Keyword,
+        Self::Operator,
+        Self::Number,
+        Self::String,
+        Self::Function,
+        Self::Interpolated,
+        Self::Error,
+    ];
+
     /// Return the recommended TextMate grammar scope for the given highlighting
     /// tag.
     pub fn tm_scope(&self) -> &'static str {
@@ -240,8 +267,10 @@ pub fn highlight(node: &LinkedNode) -> Option<Tag> {
         SyntaxKind::WhileLoop => None,
         SyntaxKind::ForLoop => None,
         SyntaxKind::ForPattern => None,
+        SyntaxKind::Destructuring => None,
         SyntaxKind::ModuleImport => None,
         SyntaxKind::ImportItems => None,
+        SyntaxKind::RenamedImportItem => None,
         SyntaxKind::ModuleInclude => None,
         SyntaxKind::LoopBreak => None,
         SyntaxKind::LoopContinue => None,
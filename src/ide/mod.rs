@@ -2,14 +2,24 @@
 
 mod analyze;
 mod complete;
+mod definition;
+mod folding;
 mod highlight;
 mod jump;
+mod rename;
+mod signature;
+mod symbols;
 mod tooltip;
 
 pub use self::analyze::analyze_labels;
 pub use self::complete::*;
+pub use self::definition::*;
+pub use self::folding::*;
 pub use self::highlight::*;
 pub use self::jump::*;
+pub use self::rename::*;
+pub use self::signature::*;
+pub use self::symbols::*;
 pub use self::tooltip::*;
 
 use std::fmt::Write;
@@ -95,3 +105,129 @@ fn summarize_font_family<'a>(variants: impl Iterator<Item = &'a FontInfo>) -> Ec
 
     detail
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use comemo::Prehashed;
+
+    use crate::diag::{bail, FileError, FileResult};
+    use crate::doc::Document;
+    use crate::eval::{Datetime, LangItems, Library, Module};
+    use crate::font::{Font, FontBook};
+    use crate::geom::{Abs, Dir};
+    use crate::model::{Content, Element, MetaElem, Styles};
+    use crate::syntax::{Source, SourceId};
+    use crate::util::{Buffer, PathExt};
+    use crate::World;
+
+    /// A world with a few in-memory source files, the first one being the
+    /// main file.
+    pub struct TestWorld {
+        sources: Vec<Source>,
+        library: Prehashed<Library>,
+        book: Prehashed<FontBook>,
+    }
+
+    impl TestWorld {
+        pub fn new(files: &[(&str, &str)]) -> Self {
+            let sources = files
+                .iter()
+                .enumerate()
+                .map(|(i, &(path, text))| {
+                    Source::new(
+                        SourceId::from_u16(i as u16),
+                        Path::new(path),
+                        text.into(),
+                    )
+                })
+                .collect();
+            Self {
+                sources,
+                library: Prehashed::new(library()),
+                book: Prehashed::new(FontBook::new()),
+            }
+        }
+    }
+
+    /// A library without any definitions whose lang items produce empty
+    /// content.
+    fn library() -> Library {
+        Library {
+            global: Module::new("global"),
+            math: Module::new("math"),
+            styles: Styles::new(),
+            items: LangItems {
+                layout: |_, _, _| Ok(Document::default()),
+                em: |_| Abs::zero(),
+                dir: |_| Dir::LTR,
+                space: Content::empty,
+                linebreak: Content::empty,
+                text: |_| Content::empty(),
+                text_func: MetaElem::func(),
+                text_str: |_| None,
+                smart_quote: |_| Content::empty(),
+                parbreak: Content::empty,
+                strong: |body| body,
+                emph: |body| body,
+                raw: |_, _, _| Content::empty(),
+                raw_languages: Vec::new,
+                link: |_| Content::empty(),
+                reference: |_, _| Content::empty(),
+                bibliography_keys: |_, _| vec![],
+                heading: |_, body| body,
+                list_item: |body| body,
+                enum_item: |_, body| body,
+                term_item: |term, description| term + description,
+                equation: |body, _| body,
+                math_align_point: Content::empty,
+                math_delimited: |open, body, close| open + body + close,
+                math_attach: |base, _, _| base,
+                math_accent: |base, _| base,
+                math_frac: |num, denom| num + denom,
+                library_method: |_, dynamic, method, _, span| {
+                    bail!(span, "type {} has no method `{}`", dynamic.type_name(), method)
+                },
+            },
+        }
+    }
+
+    impl World for TestWorld {
+        fn library(&self) -> &Prehashed<Library> {
+            &self.library
+        }
+
+        fn main(&self) -> &Source {
+            &self.sources[0]
+        }
+
+        fn resolve(&self, path: &Path) -> FileResult<SourceId> {
+            self.sources
+                .iter()
+                .find(|source| source.path() == path.normalize())
+                .map(Source::id)
+                .ok_or_else(|| FileError::NotFound(path.into()))
+        }
+
+        fn source(&self, id: SourceId) -> &Source {
+            &self.sources[id.into_u16() as usize]
+        }
+
+        fn book(&self) -> &Prehashed<FontBook> {
+            &self.book
+        }
+
+        fn font(&self, _: usize) -> Option<Font> {
+            None
+        }
+
+        fn file(&self, path: &Path) -> FileResult<Buffer> {
+            Err(FileError::NotFound(path.into()))
+        }
+
+        fn today(&self, _: Option<i64>) -> Option<Datetime> {
+            None
+        }
+    }
+}
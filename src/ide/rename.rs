@@ -0,0 +1,251 @@
+use std::ops::Range;
+
+use ecow::{eco_format, EcoString};
+
+use super::definition::{
+    find_in_scope, is_variable, label_name, reachable, resolve, visit, Definition,
+};
+use crate::diag::StrResult;
+use crate::syntax::{
+    is_id_continue, is_ident, is_math_ident, keyword, LinkedNode, Source, SourceId,
+    SyntaxKind,
+};
+use crate::World;
+
+/// Compute the edits that rename the item under the cursor.
+///
+/// Renames a `let`-bound variable or function together with all of its uses
+/// across imports, or a label together with all references to it. Fails if
+/// the new name is invalid or would collide with an existing name.
+pub fn rename(
+    world: &(dyn World + 'static),
+    source: &Source,
+    cursor: usize,
+    new_name: &str,
+) -> StrResult<Vec<(SourceId, Range<usize>, EcoString)>> {
+    let leaf = LinkedNode::new(source.root())
+        .leaf_at(cursor)
+        .ok_or("there is nothing to rename here")?;
+
+    if let Some(label) = label_name(&leaf) {
+        return rename_label(world, source, label, new_name);
+    }
+
+    if !matches!(leaf.kind(), SyntaxKind::Ident | SyntaxKind::MathIdent) {
+        Err("can only rename variables, functions and labels")?;
+    }
+
+    rename_binding(world, source, &leaf, new_name)
+}
+
+/// Rename a label and all references to it.
+fn rename_label(
+    world: &(dyn World + 'static),
+    source: &Source,
+    old_name: &str,
+    new_name: &str,
+) -> StrResult<Vec<(SourceId, Range<usize>, EcoString)>> {
+    if new_name.is_empty() || !new_name.chars().all(is_id_continue) {
+        Err(eco_format!("`{new_name}` is not a valid label name"))?;
+    }
+
+    let mut edits = vec![];
+    if new_name == old_name {
+        return Ok(edits);
+    }
+
+    let mut collision = false;
+    for id in reachable(world, source) {
+        let source = world.source(id);
+        visit(&LinkedNode::new(source.root()), &mut |node| {
+            let Some(name) = label_name(node) else { return };
+            if name == new_name {
+                collision = true;
+            } else if name == old_name {
+                let text = match node.kind() {
+                    SyntaxKind::Label => eco_format!("<{new_name}>"),
+                    _ => eco_format!("@{new_name}"),
+                };
+                edits.push((id, node.range(), text));
+            }
+        });
+    }
+
+    if collision {
+        Err(eco_format!("label `<{new_name}>` already exists"))?;
+    }
+
+    Ok(edits)
+}
+
+/// Rename a variable or function and all of its uses.
+fn rename_binding(
+    world: &(dyn World + 'static),
+    source: &Source,
+    leaf: &LinkedNode,
+    new_name: &str,
+) -> StrResult<Vec<(SourceId, Range<usize>, EcoString)>> {
+    if !is_ident(new_name) || keyword(new_name).is_some() {
+        Err(eco_format!("`{new_name}` is not a valid identifier"))?;
+    }
+
+    let target = resolve(world, source, leaf, 0)
+        .ok_or_else(|| eco_format!("cannot find the definition of `{}`", leaf.text()))?;
+
+    let Definition::Binding(def_id, def_range) = target.clone() else {
+        Err("cannot rename a module")?
+    };
+
+    let old_name: EcoString = world
+        .source(def_id)
+        .get(def_range.clone())
+        .ok_or("cannot find the definition")?
+        .into();
+
+    let mut edits = vec![];
+    if old_name == new_name {
+        return Ok(edits);
+    }
+
+    let mut error = None;
+    for id in reachable(world, source) {
+        let source = world.source(id);
+        visit(&LinkedNode::new(source.root()), &mut |node| {
+            if error.is_some()
+                || !matches!(node.kind(), SyntaxKind::Ident | SyntaxKind::MathIdent)
+            {
+                return;
+            }
+
+            let text = node.text();
+            if *text == old_name {
+                if resolve(world, source, node, 0).as_ref() != Some(&target) {
+                    return;
+                }
+
+                // A use would now refer to another binding with the new name
+                // unless the renamed binding shadows that one.
+                if node.range() != def_range || id != def_id {
+                    if let Some(existing) =
+                        find_in_scope(world, source, node, new_name, 0)
+                    {
+                        if existing.source() != def_id
+                            || existing.range().start > def_range.start
+                        {
+                            error = Some(eco_format!(
+                                "`{new_name}` is already defined in this scope"
+                            ));
+                            return;
+                        }
+                    }
+                }
+
+                if node.kind() == SyntaxKind::MathIdent && !is_math_ident(new_name) {
+                    error = Some(eco_format!("`{new_name}` cannot be used in math"));
+                    return;
+                }
+
+                edits.push((id, node.range(), new_name.into()));
+            } else if text == new_name && is_variable(node) {
+                // An existing use of the new name would now refer to the
+                // renamed binding.
+                if find_in_scope(world, source, node, &old_name, 0).as_ref()
+                    == Some(&target)
+                {
+                    error = Some(eco_format!(
+                        "renaming would shadow the existing `{new_name}`"
+                    ));
+                }
+            }
+        });
+    }
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(edits),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ide::tests::TestWorld;
+
+    const MAIN: SourceId = SourceId::from_u16(0);
+    const OTHER: SourceId = SourceId::from_u16(1);
+
+    const FILES: &[(&str, &str)] = &[
+        (
+            "/main.typ",
+            "#import \"other.typ\": f\n#let g = 1\n#f(g)\n\
+             = Intro <intro>\nSee @intro and @intro[p. 1].",
+        ),
+        ("/other.typ", "#let f(x) = x\n#f(2) @intro"),
+    ];
+
+    /// Rename the item at the first occurrence of `needle` in the main file
+    /// and return the edits as the file, byte offset and new text.
+    #[track_caller]
+    fn test(
+        needle: &str,
+        new_name: &str,
+    ) -> StrResult<Vec<(SourceId, usize, EcoString)>> {
+        let world = TestWorld::new(FILES);
+        let main = world.source(MAIN);
+        let cursor = main.text().find(needle).unwrap() + 1;
+        let mut edits: Vec<_> = rename(&world, main, cursor, new_name)?
+            .into_iter()
+            .map(|(id, range, text)| (id, range.start, text))
+            .collect();
+        edits.sort_by_key(|&(id, start, _)| (id.into_u16(), start));
+        Ok(edits)
+    }
+
+    #[test]
+    fn test_rename_binding() {
+        // A function used across an import.
+        let edits = test("f(g)", "h").unwrap();
+        assert_eq!(
+            edits,
+            [
+                (MAIN, 21, "h".into()),
+                (MAIN, 35, "h".into()),
+                (OTHER, 5, "h".into()),
+                (OTHER, 15, "h".into()),
+            ]
+        );
+
+        // A local variable.
+        let edits = test("g)", "y").unwrap();
+        assert_eq!(edits, [(MAIN, 28, "y".into()), (MAIN, 37, "y".into())]);
+
+        // Renaming to the same name changes nothing.
+        assert_eq!(test("g)", "g"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_rename_label() {
+        let edits = test("@intro", "start").unwrap();
+        assert_eq!(
+            edits,
+            [
+                (MAIN, 48, "<start>".into()),
+                (MAIN, 60, "@start".into()),
+                (MAIN, 71, "@start".into()),
+                (OTHER, 20, "@start".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_invalid() {
+        assert_eq!(test("g)", "let"), Err("`let` is not a valid identifier".into()));
+        assert_eq!(test("g)", "1x"), Err("`1x` is not a valid identifier".into()));
+        assert_eq!(test("g)", "f"), Err("renaming would shadow the existing `f`".into()));
+        assert_eq!(test("<intro>", "a b"), Err("`a b` is not a valid label name".into()));
+        assert_eq!(
+            test("Intro", "x"),
+            Err("can only rename variables, functions and labels".into())
+        );
+    }
+}
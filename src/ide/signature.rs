@@ -0,0 +1,253 @@
+use std::ops::Range;
+
+use ecow::EcoString;
+
+use super::{analyze_expr, plain_docs_sentence};
+use crate::eval::{CastInfo, ParamInfo, Value};
+use crate::syntax::{ast, LinkedNode, Source, SyntaxKind};
+use crate::World;
+
+/// Describe the signature of the function whose argument list the cursor is
+/// in.
+pub fn signature_help(
+    world: &(dyn World + 'static),
+    source: &Source,
+    cursor: usize,
+) -> Option<SignatureHelp> {
+    let leaf = LinkedNode::new(source.root()).leaf_at(cursor)?;
+
+    // Find the innermost argument list whose parentheses enclose the cursor.
+    let mut node = leaf.parent();
+    let (args, callee, set) = loop {
+        let args = node?;
+        node = args.parent();
+        if args.kind() != SyntaxKind::Args || !encloses(args, cursor) {
+            continue;
+        }
+
+        let Some(parent) = args.parent() else { continue };
+        let set = match parent.kind() {
+            SyntaxKind::FuncCall => false,
+            SyntaxKind::SetRule => true,
+            _ => continue,
+        };
+
+        let Some(callee) = parent.children().find(|child| child.is::<ast::Expr>()) else {
+            continue;
+        };
+
+        break (args, callee, set);
+    };
+
+    let values = analyze_expr(world, &callee);
+    let info = values.iter().find_map(|value| match value {
+        Value::Func(func) => func.info(),
+        _ => None,
+    })?;
+
+    // Set rules only accept settable parameters.
+    let params: Vec<_> = info
+        .params
+        .iter()
+        .filter(|param| !set || param.settable)
+        .cloned()
+        .collect();
+
+    let active = active_param(args, cursor, &params);
+
+    let mut label = EcoString::from(info.name);
+    label.push('(');
+    let mut ranges = vec![];
+    for (i, param) in params.iter().enumerate() {
+        if i > 0 {
+            label.push_str(", ");
+        }
+
+        let start = label.len();
+        if param.variadic {
+            label.push_str("..");
+        }
+        label.push_str(param.name);
+        label.push_str(": ");
+        label.push_str(&types(&param.cast).join(" | "));
+        ranges.push(start..label.len());
+    }
+    label.push(')');
+
+    if !info.returns.is_empty() && !set {
+        label.push_str(" -> ");
+        label.push_str(&info.returns.join(" | "));
+    }
+
+    Some(SignatureHelp {
+        label,
+        docs: plain_docs_sentence(info.docs),
+        params: ranges
+            .into_iter()
+            .zip(params)
+            .map(|(range, param)| ParamHelp {
+                range,
+                docs: plain_docs_sentence(param.docs),
+                default: param.default.map(|default| default().repr().into()),
+                info: param,
+            })
+            .collect(),
+        active,
+    })
+}
+
+/// The signature of a function, as shown while typing its arguments.
+#[derive(Debug, Clone)]
+pub struct SignatureHelp {
+    /// The signature, e.g. `rotate(angle: angle, ..)`.
+    pub label: EcoString,
+    /// The first sentence of the function's documentation.
+    pub docs: EcoString,
+    /// The parameters of the function.
+    pub params: Vec<ParamHelp>,
+    /// The index of the parameter the cursor is at.
+    pub active: Option<usize>,
+}
+
+/// A parameter in a signature.
+#[derive(Debug, Clone)]
+pub struct ParamHelp {
+    /// The byte range of the parameter in the signature's label.
+    pub range: Range<usize>,
+    /// The first sentence of the parameter's documentation.
+    pub docs: EcoString,
+    /// The default value of the parameter, if it is known.
+    pub default: Option<EcoString>,
+    /// Details about the parameter.
+    pub info: ParamInfo,
+}
+
+/// Whether the cursor is between the parentheses of an argument list.
+fn encloses(args: &LinkedNode, cursor: usize) -> bool {
+    let mut children = args.children();
+    let Some(open) = children.next() else { return false };
+    if open.kind() != SyntaxKind::LeftParen || open.range().end > cursor {
+        return false;
+    }
+
+    children
+        .find(|child| child.kind() == SyntaxKind::RightParen)
+        .map_or(true, |close| cursor <= close.offset())
+}
+
+/// Determine which parameter the argument at the cursor belongs to.
+fn active_param(args: &LinkedNode, cursor: usize, params: &[ParamInfo]) -> Option<usize> {
+    let mut positional = 0;
+    let mut current = None;
+    for child in args.children() {
+        if child.offset() >= cursor || child.kind() == SyntaxKind::RightParen {
+            break;
+        }
+
+        if child.kind() == SyntaxKind::Comma {
+            if let Some(ast::Arg::Pos(_)) = current.take() {
+                positional += 1;
+            }
+        } else if let Some(arg) = child.cast::<ast::Arg>() {
+            current = Some(arg);
+        }
+    }
+
+    match current {
+        Some(ast::Arg::Named(named)) => {
+            let name = named.name();
+            params
+                .iter()
+                .position(|param| param.named && param.name == name.as_str())
+        }
+        Some(ast::Arg::Spread(_)) => None,
+        Some(ast::Arg::Pos(_)) | None => params
+            .iter()
+            .enumerate()
+            .filter(|(_, param)| param.positional)
+            .enumerate()
+            .find(|(k, (_, param))| {
+                *k == positional || (*k < positional && param.variadic)
+            })
+            .map(|(_, (i, _))| i),
+    }
+}
+
+/// The names of the types a parameter accepts.
+fn types(info: &CastInfo) -> Vec<&'static str> {
+    fn accumulate(info: &CastInfo, types: &mut Vec<&'static str>) {
+        let ty = match info {
+            CastInfo::Any => "any",
+            CastInfo::Value(value, _) => value.type_name(),
+            CastInfo::Type(ty) => *ty,
+            CastInfo::Union(options) => {
+                for option in options {
+                    accumulate(option, types);
+                }
+                return;
+            }
+        };
+
+        if !types.contains(&ty) {
+            types.push(ty);
+        }
+    }
+
+    let mut types = vec![];
+    accumulate(info, &mut types);
+    types
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_param() {
+        fn param(name: &'static str, positional: bool, variadic: bool) -> ParamInfo {
+            ParamInfo {
+                name,
+                docs: "",
+                cast: CastInfo::Any,
+                positional,
+                named: !positional,
+                variadic,
+                required: positional && !variadic,
+                settable: false,
+                default: None,
+            }
+        }
+
+        let params = [
+            param("x", true, false),
+            param("y", true, false),
+            param("size", false, false),
+            param("rest", true, true),
+        ];
+
+        #[track_caller]
+        fn test(text: &str, cursor: usize, params: &[ParamInfo], active: Option<usize>) {
+            let source = Source::detached(text);
+            let leaf = LinkedNode::new(source.root()).leaf_at(cursor).unwrap();
+            let mut node = leaf;
+            while node.kind() != SyntaxKind::Args {
+                node = node.parent().unwrap().clone();
+            }
+            assert_eq!(active_param(&node, cursor, params), active);
+        }
+
+        // Positional arguments, including those collected by a sink.
+        test("#f(1, 2, 3, 4)", 3, &params, Some(0));
+        test("#f(1, 2, 3, 4)", 6, &params, Some(1));
+        test("#f(1, 2, 3, 4)", 9, &params, Some(3));
+        test("#f(1, 2, 3, 4)", 12, &params, Some(3));
+
+        // Named arguments and positional ones after them.
+        test("#f(1, size: 2, 3)", 8, &params, Some(2));
+        test("#f(1, size: 2, 3)", 16, &params, Some(1));
+        test("#f(unknown: 2)", 5, &params, None);
+
+        // Spread arguments can't be attributed to a single parameter.
+        test("#f(1, ..args)", 9, &params, None);
+    }
+}
@@ -0,0 +1,252 @@
+use std::num::NonZeroUsize;
+use std::ops::Range;
+
+use ecow::{eco_format, EcoString};
+
+use crate::syntax::ast::{self, AstNode};
+use crate::syntax::{LinkedNode, Source, SyntaxKind};
+
+/// Produce a hierarchical outline of a source file.
+///
+/// Headings contain everything up to the next heading of the same or a
+/// higher level. Besides headings, the outline lists top-level functions,
+/// variables and rules as well as labels.
+pub fn symbols(source: &Source) -> Vec<Symbol> {
+    let root = LinkedNode::new(source.root());
+    let mut stack: Vec<(NonZeroUsize, Symbol)> = vec![];
+    let mut symbols = vec![];
+
+    for child in root.children() {
+        if let Some(heading) = child.cast::<ast::Heading>() {
+            let level = heading.level();
+            close_sections(&mut stack, &mut symbols, level, child.offset());
+
+            let body = child.children().find(|node| node.kind() == SyntaxKind::Markup);
+            let name = heading.body().as_untyped().clone().into_text();
+            let selection = body.map_or(child.range(), |body| body.range());
+            let symbol = Symbol {
+                name: name.trim().into(),
+                kind: SymbolKind::Heading(level),
+                range: child.range(),
+                selection,
+                children: vec![],
+            };
+
+            // The heading's own labels belong to the heading.
+            stack.push((level, symbol));
+            collect(&child, &mut stack.last_mut().unwrap().1.children);
+            continue;
+        }
+
+        let target = match stack.last_mut() {
+            Some((_, section)) => &mut section.children,
+            None => &mut symbols,
+        };
+
+        collect(&child, target);
+    }
+
+    close_sections(
+        &mut stack,
+        &mut symbols,
+        NonZeroUsize::new(1).unwrap(),
+        source.len_bytes(),
+    );
+    symbols
+}
+
+/// An item in the outline of a source file.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Symbol {
+    /// The name of the item.
+    pub name: EcoString,
+    /// What kind of item this is.
+    pub kind: SymbolKind,
+    /// The byte range of the whole item, e.g. a heading's section.
+    pub range: Range<usize>,
+    /// The byte range that should be revealed when the item is selected,
+    /// e.g. a heading's body.
+    pub selection: Range<usize>,
+    /// The items nested in this one.
+    pub children: Vec<Symbol>,
+}
+
+/// What kind of item a symbol is.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SymbolKind {
+    /// A heading with the given level.
+    Heading(NonZeroUsize),
+    /// A function defined with `let`.
+    Function,
+    /// A variable defined with `let`.
+    Variable,
+    /// A label.
+    Label,
+    /// A set rule.
+    SetRule,
+    /// A show rule.
+    ShowRule,
+}
+
+/// Close all open sections with the given or a deeper level, ending them at
+/// `end`.
+fn close_sections(
+    stack: &mut Vec<(NonZeroUsize, Symbol)>,
+    symbols: &mut Vec<Symbol>,
+    level: NonZeroUsize,
+    end: usize,
+) {
+    while stack.last().map_or(false, |&(open, _)| open >= level) {
+        let (_, mut section) = stack.pop().unwrap();
+        section.range.end = end;
+        match stack.last_mut() {
+            Some((_, parent)) => parent.children.push(section),
+            None => symbols.push(section),
+        }
+    }
+}
+
+/// Collect the symbols in a top-level node and its descendants.
+fn collect(node: &LinkedNode, symbols: &mut Vec<Symbol>) {
+    match node.cast::<ast::Expr>() {
+        Some(ast::Expr::Let(binding)) => {
+            let kind = match binding.init() {
+                Some(ast::Expr::Closure(_)) => SymbolKind::Function,
+                _ => SymbolKind::Variable,
+            };
+
+            for ident in binding.pattern().idents() {
+                let Some(leaf) = node.find(ident.span()) else { continue };
+                symbols.push(Symbol {
+                    name: ident.get().clone(),
+                    kind,
+                    range: node.range(),
+                    selection: leaf.range(),
+                    children: vec![],
+                });
+            }
+        }
+        Some(ast::Expr::Set(set)) => {
+            symbols.push(Symbol {
+                name: eco_format!(
+                    "set {}",
+                    set.target().as_untyped().clone().into_text()
+                ),
+                kind: SymbolKind::SetRule,
+                range: node.range(),
+                selection: node.range(),
+                children: vec![],
+            });
+        }
+        Some(ast::Expr::Show(show)) => {
+            let name = match show.selector() {
+                Some(selector) => {
+                    eco_format!("show {}", selector.as_untyped().clone().into_text())
+                }
+                None => "show".into(),
+            };
+
+            symbols.push(Symbol {
+                name,
+                kind: SymbolKind::ShowRule,
+                range: node.range(),
+                selection: node.range(),
+                children: vec![],
+            });
+        }
+        _ => {}
+    }
+
+    labels(node, symbols);
+}
+
+/// Collect all labels in a node and its descendants.
+fn labels(node: &LinkedNode, symbols: &mut Vec<Symbol>) {
+    if node.kind() == SyntaxKind::Label {
+        symbols.push(Symbol {
+            name: node.text().clone(),
+            kind: SymbolKind::Label,
+            range: node.range(),
+            selection: node.range(),
+            children: vec![],
+        });
+    }
+
+    for child in node.children() {
+        labels(&child, symbols);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols() {
+        use SymbolKind::*;
+
+        fn flatten(
+            symbols: &[Symbol],
+            depth: usize,
+            out: &mut Vec<(usize, EcoString, SymbolKind)>,
+        ) {
+            for symbol in symbols {
+                out.push((depth, symbol.name.clone(), symbol.kind));
+                flatten(&symbol.children, depth + 1, out);
+            }
+        }
+
+        #[track_caller]
+        fn test(text: &str, goal: &[(usize, &str, SymbolKind)]) {
+            let mut out = vec![];
+            flatten(&symbols(&Source::detached(text)), 0, &mut out);
+            let goal: Vec<_> = goal
+                .iter()
+                .map(|&(depth, name, kind)| (depth, name.into(), kind))
+                .collect();
+            assert_eq!(out, goal);
+        }
+
+        let h = |level| Heading(NonZeroUsize::new(level).unwrap());
+
+        // Headings nest sections of deeper levels.
+        test(
+            "= A\n== B\n=== C\n== D\n= E",
+            &[
+                (0, "A", h(1)),
+                (1, "B", h(2)),
+                (2, "C", h(3)),
+                (1, "D", h(2)),
+                (0, "E", h(1)),
+            ],
+        );
+
+        // Bindings, rules and labels belong to the enclosing section.
+        test(
+            "#let f(x) = x\n= A\n#let (u, v) = (1, 2)\n== B <b>\n\
+             #set text(red)\n#show heading: it => it",
+            &[
+                (0, "f", Function),
+                (0, "A", h(1)),
+                (1, "u", Variable),
+                (1, "v", Variable),
+                (1, "B", h(2)),
+                (2, "<b>", Label),
+                (2, "set text", SetRule),
+                (2, "show heading", ShowRule),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_symbol_ranges() {
+        let text = "= A\ntext\n== B\n= C\n";
+        let source = Source::detached(text);
+        let symbols = symbols(&source);
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(&text[symbols[0].range.clone()], "= A\ntext\n== B\n");
+        assert_eq!(&text[symbols[0].selection.clone()], "A");
+        assert_eq!(&text[symbols[0].children[0].range.clone()], "== B\n");
+        assert_eq!(&text[symbols[1].range.clone()], "= C\n");
+    }
+}
@@ -17,7 +17,7 @@
 //!   per page with items at fixed positions.
 //! - **Exporting:**
 //!   These frames can finally be exported into an output format (currently
-//!   supported are [PDF] and [raster images]).
+//!   supported are [PDF], [raster images] and [SVG]).
 //!
 //! [tokens]: syntax::SyntaxKind
 //! [parsed]: syntax::parse
@@ -31,6 +31,7 @@
 //! [frame]: doc::Frame
 //! [PDF]: export::pdf
 //! [raster images]: export::render
+//! [SVG]: export::svg
 
 #![recursion_limit = "1000"]
 
@@ -51,27 +52,35 @@ pub mod image;
 pub mod model;
 pub mod syntax;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use comemo::{Prehashed, Track};
 
-use crate::diag::{FileResult, SourceResult};
+use crate::diag::{FileError, FileResult, SourceError, SourceResult};
 use crate::doc::Document;
-use crate::eval::{Library, Route, Tracer};
+use crate::eval::{Datetime, Library, PackageSpec, Route, Tracer};
 use crate::font::{Font, FontBook};
 use crate::syntax::{Source, SourceId};
-use crate::util::Buffer;
+use crate::util::{timing, Buffer};
 
 /// Compile a source file into a fully layouted document.
-pub fn compile(world: &(dyn World + 'static)) -> SourceResult<Document> {
+///
+/// Returns the document along with the warnings that occurred during
+/// compilation.
+pub fn compile(
+    world: &(dyn World + 'static),
+) -> SourceResult<(Document, Vec<SourceError>)> {
     // Evaluate the source file into a module.
     let route = Route::default();
     let mut tracer = Tracer::default();
+    timing::memo_call("eval");
     let module =
         eval::eval(world.track(), route.track(), tracer.track_mut(), world.main())?;
 
     // Typeset the module's contents.
-    model::typeset(world.track(), tracer.track_mut(), &module.content())
+    timing::memo_call("typeset");
+    let document = model::typeset(world.track(), tracer.track_mut(), &module.content())?;
+    Ok((document, tracer.take_warnings()))
 }
 
 /// The environment in which typesetting occurs.
@@ -104,4 +113,38 @@ pub trait World {
 
     /// Try to access a file at a path.
     fn file(&self, path: &Path) -> FileResult<Buffer>;
+
+    /// Get the current date.
+    ///
+    /// If no offset is specified, the local date should be chosen. Otherwise,
+    /// the UTC date should be chosen with the corresponding offset in hours.
+    ///
+    /// If this function returns `None`, Typst's `datetime.today()` will
+    /// produce an error.
+    fn today(&self, offset: Option<i64>) -> Option<Datetime>;
+
+    /// Try to find the root directory of a package.
+    ///
+    /// Defaults to an error, as not all environments support packages.
+    fn package(&self, spec: &PackageSpec) -> FileResult<PathBuf> {
+        Err(FileError::PackageNotFound(spec.to_string().into()))
+    }
+
+    /// The directory relative to which absolute paths in the given source file
+    /// are resolved.
+    ///
+    /// This is the root directory of the package for source files that are part
+    /// of one. Defaults to [`root`](Self::root).
+    fn root_of(&self, id: SourceId) -> PathBuf {
+        let _ = id;
+        self.root().into()
+    }
+
+    /// The maximum number of layout iterations to attempt until all
+    /// introspections stabilize.
+    ///
+    /// Defaults to five.
+    fn max_iterations(&self) -> usize {
+        5
+    }
 }
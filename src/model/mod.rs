@@ -15,10 +15,12 @@ pub use self::styles::*;
 pub use typst_macros::element;
 
 use comemo::{Constraint, Track, Tracked, TrackedMut};
+use ecow::eco_format;
 
 use crate::diag::SourceResult;
 use crate::doc::Document;
 use crate::eval::Tracer;
+use crate::util::timing;
 use crate::World;
 
 /// Typeset content into a fully layouted document.
@@ -28,18 +30,24 @@ pub fn typeset(
     mut tracer: TrackedMut<Tracer>,
     content: &Content,
 ) -> SourceResult<Document> {
+    timing::memo_miss("typeset");
     let library = world.library();
     let styles = StyleChain::new(&library.styles);
 
     let mut document;
     let mut iter = 0;
     let mut introspector = Introspector::new(&[]);
+    let max = world.max_iterations().max(1);
 
     // Relayout until all introspections stabilize.
-    // If that doesn't happen within five attempts, we give up.
+    // If that doesn't happen within the maximum number of attempts, we give up
+    // and point at the introspections whose results kept changing.
     loop {
+        let _scope = timing::scope("typeset", || eco_format!("iteration {}", iter + 1));
         let constraint = Constraint::new();
         let mut provider = StabilityProvider::new();
+        tracer.relayout();
+
         let mut vt = Vt {
             world,
             tracer: TrackedMut::reborrow_mut(&mut tracer),
@@ -52,7 +60,12 @@ pub fn typeset(
 
         introspector = Introspector::new(&document.pages);
 
-        if iter >= 5 || introspector.valid(&constraint) {
+        if introspector.valid(&constraint) {
+            break;
+        }
+
+        if iter >= max {
+            tracer.diverged(iter);
             break;
         }
     }
@@ -8,7 +8,7 @@ use super::{Content, ElemFunc, Element, Label, Vt};
 use crate::diag::{SourceResult, Trace, Tracepoint};
 use crate::eval::{cast_from_value, Args, Cast, Dict, Func, Regex, Value, Vm};
 use crate::syntax::Span;
-use crate::util::pretty_array_like;
+use crate::util::{pretty_array_like, timing};
 
 /// A list of style properties.
 #[derive(Default, PartialEq, Clone, Hash)]
@@ -203,6 +203,7 @@ impl Recipe {
 
     /// Apply the recipe to the given content.
     pub fn apply_vm(&self, vm: &mut Vm, content: Content) -> SourceResult<Content> {
+        let _scope = timing::scope("show", || content.func().name().into());
         match &self.transform {
             Transform::Content(content) => Ok(content.clone()),
             Transform::Func(func) => {
@@ -221,6 +222,7 @@ impl Recipe {
 
     /// Apply the recipe to the given content.
     pub fn apply_vt(&self, vt: &mut Vt, content: Content) -> SourceResult<Content> {
+        let _scope = timing::scope("show", || content.func().name().into());
         match &self.transform {
             Transform::Content(content) => Ok(content.clone()),
             Transform::Func(func) => {
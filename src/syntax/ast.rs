@@ -1162,6 +1162,12 @@ impl Named {
     pub fn expr(&self) -> Expr {
         self.0.cast_last_match().unwrap_or_default()
     }
+
+    /// The right-hand side of the pair as a pattern: `(x, y)` in
+    /// `point: (x, y)`.
+    pub fn pattern(&self) -> Pattern {
+        self.0.cast_last_match().unwrap_or_default()
+    }
 }
 
 node! {
@@ -1572,6 +1578,100 @@ impl AstNode for Param {
     }
 }
 
+/// A pattern that values can be bound to.
+#[derive(Debug, Clone, Hash)]
+pub enum Pattern {
+    /// A single identifier: `x`.
+    Normal(Ident),
+    /// A destructuring pattern: `(x, ..rest)`.
+    Destructuring(Destructuring),
+}
+
+impl AstNode for Pattern {
+    fn from_untyped(node: &SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::Ident => node.cast().map(Self::Normal),
+            SyntaxKind::Destructuring => node.cast().map(Self::Destructuring),
+            _ => Option::None,
+        }
+    }
+
+    fn as_untyped(&self) -> &SyntaxNode {
+        match self {
+            Self::Normal(v) => v.as_untyped(),
+            Self::Destructuring(v) => v.as_untyped(),
+        }
+    }
+}
+
+impl Pattern {
+    /// All identifiers the pattern binds, in order of appearance.
+    pub fn idents(&self) -> Vec<Ident> {
+        match self {
+            Self::Normal(ident) => vec![ident.clone()],
+            Self::Destructuring(destructuring) => destructuring.idents(),
+        }
+    }
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        Self::Normal(Ident::default())
+    }
+}
+
+node! {
+    /// A destructuring pattern: `(x, ..rest, name: n)`.
+    Destructuring
+}
+
+impl Destructuring {
+    /// The items of the pattern.
+    pub fn items(&self) -> impl DoubleEndedIterator<Item = DestructuringItem> + '_ {
+        self.0.children().filter_map(SyntaxNode::cast)
+    }
+
+    /// All identifiers the pattern binds, in order of appearance.
+    pub fn idents(&self) -> Vec<Ident> {
+        self.items()
+            .flat_map(|item| match item {
+                DestructuringItem::Pattern(pattern) => pattern.idents(),
+                DestructuringItem::Named(named) => named.pattern().idents(),
+                DestructuringItem::Sink(ident) => vec![ident],
+            })
+            .collect()
+    }
+}
+
+/// An item in a destructuring pattern.
+#[derive(Debug, Clone, Hash)]
+pub enum DestructuringItem {
+    /// A positional item: `x` or `(a, b)`.
+    Pattern(Pattern),
+    /// A named item: `name: n`.
+    Named(Named),
+    /// A destructuring sink: `..rest`.
+    Sink(Ident),
+}
+
+impl AstNode for DestructuringItem {
+    fn from_untyped(node: &SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::Named => node.cast().map(Self::Named),
+            SyntaxKind::Spread => node.cast_first_match().map(Self::Sink),
+            _ => node.cast().map(Self::Pattern),
+        }
+    }
+
+    fn as_untyped(&self) -> &SyntaxNode {
+        match self {
+            Self::Pattern(v) => v.as_untyped(),
+            Self::Named(v) => v.as_untyped(),
+            Self::Sink(v) => v.as_untyped(),
+        }
+    }
+}
+
 node! {
     /// A let binding: `let x = 1`.
     LetBinding
@@ -1579,7 +1679,14 @@ node! {
 
 impl LetBinding {
     /// The binding to assign to.
+    ///
+    /// For destructuring bindings like `let (x, y) = p`, this is the default
+    /// identifier. Use [`pattern`](Self::pattern) to access all bindings.
     pub fn binding(&self) -> Ident {
+        if self.0.cast_first_match::<Destructuring>().is_some() {
+            return Ident::default();
+        }
+
         match self.0.cast_first_match() {
             Some(Expr::Ident(binding)) => binding,
             Some(Expr::Closure(closure)) => closure.name().unwrap_or_default(),
@@ -1587,9 +1694,24 @@ impl LetBinding {
         }
     }
 
+    /// The pattern to assign to.
+    pub fn pattern(&self) -> Pattern {
+        match self.0.cast_first_match::<Destructuring>() {
+            Some(destructuring) => Pattern::Destructuring(destructuring),
+            Option::None => Pattern::Normal(self.binding()),
+        }
+    }
+
     /// The expression the binding is initialized with.
     pub fn init(&self) -> Option<Expr> {
-        if self.0.cast_first_match::<Ident>().is_some() {
+        if self.0.cast_first_match::<Destructuring>().is_some() {
+            // This is a destructuring binding like `let (x, y) = p`.
+            self.0
+                .children()
+                .skip_while(|child| child.kind() != SyntaxKind::Destructuring)
+                .skip(1)
+                .find_map(SyntaxNode::cast)
+        } else if self.0.cast_first_match::<Ident>().is_some() {
             // This is a normal binding like `let x = 1`.
             self.0.children().filter_map(SyntaxNode::cast).nth(1)
         } else {
@@ -1711,24 +1833,23 @@ impl ForLoop {
 }
 
 node! {
-    /// A for loop's destructuring pattern: `x` or `x, y`.
+    /// A for loop's destructuring pattern: `x`, `x, y` or `(x, y)`.
     ForPattern
 }
 
 impl ForPattern {
     /// The key part of the pattern: index for arrays, name for dictionaries.
     pub fn key(&self) -> Option<Ident> {
-        let mut children = self.0.children().filter_map(SyntaxNode::cast);
+        let mut children = self.0.children().filter_map(SyntaxNode::cast::<Pattern>);
         let key = children.next();
-        if children.next().is_some() {
-            key
-        } else {
-            Option::None
+        match (key, children.next()) {
+            (Some(Pattern::Normal(key)), Some(_)) => Some(key),
+            _ => Option::None,
         }
     }
 
     /// The value part of the pattern.
-    pub fn value(&self) -> Ident {
+    pub fn value(&self) -> Pattern {
         self.0.cast_last_match().unwrap_or_default()
     }
 }
@@ -1744,6 +1865,15 @@ impl ModuleImport {
         self.0.cast_first_match().unwrap_or_default()
     }
 
+    /// The name the module is bound to when renamed: `util` in
+    /// `import "utils.typ" as util`.
+    pub fn new_name(&self) -> Option<Ident> {
+        self.0
+            .children()
+            .skip_while(|child| child.kind() != SyntaxKind::As)
+            .find_map(SyntaxNode::cast)
+    }
+
     /// The items to be imported.
     pub fn imports(&self) -> Option<Imports> {
         self.0.children().find_map(|node| match node.kind() {
@@ -1763,7 +1893,68 @@ pub enum Imports {
     /// All items in the scope of the file should be imported.
     Wildcard,
     /// The specified items from the file should be imported.
-    Items(Vec<Ident>),
+    Items(Vec<ImportItem>),
+}
+
+/// An item to import from a module.
+#[derive(Debug, Clone, Hash)]
+pub enum ImportItem {
+    /// A non-renamed import: `a`.
+    Simple(Ident),
+    /// A renamed import: `a as b`.
+    Renamed(RenamedImportItem),
+}
+
+impl AstNode for ImportItem {
+    fn from_untyped(node: &SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::Ident => node.cast().map(Self::Simple),
+            SyntaxKind::RenamedImportItem => node.cast().map(Self::Renamed),
+            _ => Option::None,
+        }
+    }
+
+    fn as_untyped(&self) -> &SyntaxNode {
+        match self {
+            Self::Simple(v) => v.as_untyped(),
+            Self::Renamed(v) => v.as_untyped(),
+        }
+    }
+}
+
+impl ImportItem {
+    /// The name of the imported item within its module.
+    pub fn original_name(&self) -> Ident {
+        match self {
+            Self::Simple(name) => name.clone(),
+            Self::Renamed(renamed) => renamed.original_name(),
+        }
+    }
+
+    /// The name the item is bound to in the importing scope.
+    pub fn bound_name(&self) -> Ident {
+        match self {
+            Self::Simple(name) => name.clone(),
+            Self::Renamed(renamed) => renamed.new_name(),
+        }
+    }
+}
+
+node! {
+    /// A renamed import item: `a as b`.
+    RenamedImportItem
+}
+
+impl RenamedImportItem {
+    /// The name of the item within its module: `a`.
+    pub fn original_name(&self) -> Ident {
+        self.0.cast_first_match().unwrap_or_default()
+    }
+
+    /// The name the item is bound to: `b`.
+    pub fn new_name(&self) -> Ident {
+        self.0.cast_last_match().unwrap_or_default()
+    }
 }
 
 node! {
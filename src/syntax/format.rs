@@ -0,0 +1,397 @@
+use super::{parse, split_newlines, SyntaxKind, SyntaxNode};
+use crate::diag::StrResult;
+
+/// The column after which call chains are wrapped.
+const MAX_WIDTH: usize = 80;
+
+/// Format a Typst source file.
+///
+/// Normalizes the spacing in code, indents multi-line blocks and argument
+/// lists and wraps long call chains. Markup is left untouched. Fails if the
+/// file has syntax errors.
+pub fn format(text: &str) -> StrResult<String> {
+    let root = parse(text);
+    if root.erroneous() {
+        Err("cannot format a file with syntax errors")?;
+    }
+
+    let mut formatter = Formatter::default();
+    formatter.markup(&root);
+
+    // Only the spacing in code may change. If anything else differs, the
+    // formatter has a bug and we rather don't touch the file.
+    let reparsed = parse(&formatter.out);
+    if reparsed.erroneous() || tokens(&root) != tokens(&reparsed) {
+        Err("formatting would change the meaning of the file")?;
+    }
+
+    Ok(formatter.out)
+}
+
+/// Formats a syntax tree into a string.
+#[derive(Default)]
+struct Formatter {
+    /// The formatted text.
+    out: String,
+    /// The indentation of new lines in the enclosing blocks.
+    indents: Vec<String>,
+}
+
+impl Formatter {
+    /// Format markup. Only embedded code is formatted.
+    fn markup(&mut self, node: &SyntaxNode) {
+        if node.kind() == SyntaxKind::Equation || node.children().len() == 0 {
+            self.out.push_str(&node.clone().into_text());
+            return;
+        }
+
+        // The indentation of code in markup restarts from the markup's lines.
+        let indents = std::mem::take(&mut self.indents);
+        let mut embedded = false;
+        for child in node.children() {
+            if embedded {
+                self.code(child, false);
+            } else {
+                self.markup(child);
+            }
+            embedded = child.kind() == SyntaxKind::Hashtag;
+        }
+        self.indents = indents;
+    }
+
+    /// Format code. Newlines are only allowed freely in code if it is
+    /// `nested` in a code block or parentheses.
+    fn code(&mut self, node: &SyntaxNode, nested: bool) {
+        match node.kind() {
+            _ if node.children().len() == 0 => self.out.push_str(node.text()),
+            SyntaxKind::Equation => self.out.push_str(&node.clone().into_text()),
+            SyntaxKind::ContentBlock => {
+                for child in node.children() {
+                    self.markup(child);
+                }
+            }
+            SyntaxKind::CodeBlock => {
+                let mut items = vec![];
+                for child in node.children() {
+                    match child.kind() {
+                        SyntaxKind::Code => items.extend(child.children()),
+                        SyntaxKind::LeftBrace | SyntaxKind::RightBrace => {}
+                        _ => items.push(child),
+                    }
+                }
+                self.delimited(SyntaxKind::Code, "{", &items, "}", true);
+            }
+            SyntaxKind::Args
+            | SyntaxKind::Array
+            | SyntaxKind::Dict
+            | SyntaxKind::Params
+            | SyntaxKind::Destructuring
+            | SyntaxKind::Parenthesized => self.collection(node),
+            SyntaxKind::FuncCall | SyntaxKind::FieldAccess => {
+                let indent = (nested && self.wraps(node))
+                    .then(|| format!("{}  ", self.line_indent()));
+                self.chain(node, nested, indent.as_deref());
+            }
+            _ => {
+                let children: Vec<_> = node.children().collect();
+                self.sequence(node.kind(), &children, nested, false);
+            }
+        }
+    }
+
+    /// Format a parenthesized collection, possibly followed by content
+    /// blocks.
+    fn collection(&mut self, node: &SyntaxNode) {
+        let children: Vec<_> = node.children().collect();
+        let close = children
+            .iter()
+            .position(|child| child.kind() == SyntaxKind::RightParen);
+        match (children.first().map(|child| child.kind()), close) {
+            (Some(SyntaxKind::LeftParen), Some(close)) => {
+                self.delimited(node.kind(), "(", &children[1..close], ")", false);
+                for child in &children[close + 1..] {
+                    self.code(child, true);
+                }
+            }
+            // Parameters without parentheses and arguments that consist only
+            // of content blocks may not be spaced out.
+            _ => {
+                for child in children {
+                    self.code(child, true);
+                }
+            }
+        }
+    }
+
+    /// Format items between delimiters. If there is a line break between the
+    /// items, they are spread over multiple indented lines.
+    fn delimited(
+        &mut self,
+        parent: SyntaxKind,
+        open: &str,
+        items: &[&SyntaxNode],
+        close: &str,
+        padded: bool,
+    ) {
+        let outer = self.line_indent();
+        self.out.push_str(open);
+
+        if items.iter().all(|item| item.kind() == SyntaxKind::Space) {
+            self.out.push_str(close);
+            return;
+        }
+
+        if items.iter().any(|item| is_break(item)) {
+            let inner = format!("{outer}  ");
+            self.out.push('\n');
+            self.out.push_str(&inner);
+            self.indents.push(inner);
+            self.sequence(parent, items, true, parent != SyntaxKind::Code);
+            self.indents.pop();
+            self.out.push('\n');
+            self.out.push_str(&outer);
+        } else {
+            if padded {
+                self.out.push(' ');
+            }
+            self.sequence(parent, items, true, false);
+            if padded {
+                self.out.push(' ');
+            }
+        }
+
+        self.out.push_str(close);
+    }
+
+    /// Format a sequence of nodes, normalizing the spaces between them.
+    /// Spaces at the start and end of the sequence are dropped.
+    fn sequence(
+        &mut self,
+        parent: SyntaxKind,
+        items: &[&SyntaxNode],
+        nested: bool,
+        break_after_comma: bool,
+    ) {
+        let mut prev: Option<&SyntaxNode> = None;
+        let mut space = None;
+        for &item in items {
+            if item.kind() == SyntaxKind::Space {
+                space = Some(item);
+                continue;
+            }
+
+            let before = space.take();
+            if let Some(prev) = prev {
+                match before.map(|space| lines(space.text())) {
+                    Some(count) if count > 0 => self.newline(count),
+                    // Comments stay on the line of the item they follow.
+                    _ if break_after_comma
+                        && prev.kind() == SyntaxKind::Comma
+                        && !item.kind().is_trivia() =>
+                    {
+                        self.newline(1)
+                    }
+                    _ if spaced(parent, prev, item) => self.out.push(' '),
+                    _ => {}
+                }
+            }
+
+            self.code(item, nested);
+            prev = Some(item);
+        }
+    }
+
+    /// Format a call chain. If an `indent` is given, the chain is wrapped
+    /// with a line break before each dot.
+    fn chain(&mut self, node: &SyntaxNode, nested: bool, indent: Option<&str>) {
+        match node.kind() {
+            SyntaxKind::FuncCall => {
+                for child in node.children() {
+                    if child.kind() == SyntaxKind::Args {
+                        self.collection(child);
+                    } else {
+                        self.chain(child, nested, indent);
+                    }
+                }
+            }
+            SyntaxKind::FieldAccess => {
+                let mut children =
+                    node.children().filter(|child| child.kind() != SyntaxKind::Space);
+                if let Some(target) = children.next() {
+                    self.chain(target, nested, indent);
+                }
+
+                if let Some(indent) = indent {
+                    self.out.push('\n');
+                    self.out.push_str(indent);
+                }
+
+                for child in children {
+                    self.code(child, nested);
+                }
+            }
+            _ => self.code(node, nested),
+        }
+    }
+
+    /// Whether to wrap a call chain: Either because it is already wrapped or
+    /// because it doesn't fit into a line.
+    fn wraps(&self, node: &SyntaxNode) -> bool {
+        let mut dots = 0;
+        let mut wrapped = false;
+        let mut link = node;
+        loop {
+            match link.kind() {
+                SyntaxKind::FieldAccess => {
+                    dots += 1;
+                    wrapped |= link.children().any(is_break);
+                }
+                SyntaxKind::FuncCall => {}
+                _ => break,
+            }
+
+            let Some(first) = link.children().next() else { break };
+            link = first;
+        }
+
+        if dots < 2 {
+            return false;
+        }
+
+        if wrapped {
+            return true;
+        }
+
+        let mut single = Formatter { out: String::new(), indents: self.indents.clone() };
+        single.chain(node, true, None);
+        let width = single.out.lines().next().map_or(0, |line| line.chars().count());
+        self.column() + width > MAX_WIDTH
+    }
+
+    /// Start new lines at the current indentation.
+    fn newline(&mut self, count: usize) {
+        let indent = match self.indents.last() {
+            Some(indent) => indent.clone(),
+            None => self.line_indent(),
+        };
+
+        for _ in 0..count.min(2) {
+            self.out.push('\n');
+        }
+
+        self.out.push_str(&indent);
+    }
+
+    /// The text of the current line.
+    fn line(&self) -> &str {
+        let start = self.out.rfind('\n').map_or(0, |i| i + 1);
+        &self.out[start..]
+    }
+
+    /// The leading whitespace of the current line.
+    fn line_indent(&self) -> String {
+        let line = self.line();
+        line[..line.len() - line.trim_start().len()].into()
+    }
+
+    /// The column at which the next character is written.
+    fn column(&self) -> usize {
+        self.line().chars().count()
+    }
+}
+
+/// Whether to put a space between two adjacent nodes in code.
+fn spaced(parent: SyntaxKind, prev: &SyntaxNode, next: &SyntaxNode) -> bool {
+    match (prev.kind(), next.kind()) {
+        (_, SyntaxKind::Comma | SyntaxKind::Semicolon | SyntaxKind::Colon) => false,
+        (_, SyntaxKind::Args) => false,
+        (SyntaxKind::Dots, _) => false,
+        (SyntaxKind::Plus | SyntaxKind::Minus, _) => parent != SyntaxKind::Unary,
+        (SyntaxKind::Ident, SyntaxKind::Params) => parent != SyntaxKind::Closure,
+        _ => true,
+    }
+}
+
+/// Whether a node is a space with a line break.
+fn is_break(node: &SyntaxNode) -> bool {
+    node.kind() == SyntaxKind::Space && lines(node.text()) > 0
+}
+
+/// The number of line breaks in a string.
+fn lines(text: &str) -> usize {
+    split_newlines(text).len() - 1
+}
+
+/// The leaves that formatting must preserve: Everything except for spaces in
+/// code.
+fn tokens(root: &SyntaxNode) -> Vec<(SyntaxKind, &str)> {
+    #[derive(Copy, Clone, Eq, PartialEq)]
+    enum Mode {
+        Markup,
+        Code,
+        Verbatim,
+    }
+
+    fn collect<'a>(
+        node: &'a SyntaxNode,
+        mode: Mode,
+        tokens: &mut Vec<(SyntaxKind, &'a str)>,
+    ) {
+        if node.children().len() == 0 {
+            if mode != Mode::Code || node.kind() != SyntaxKind::Space {
+                tokens.push((node.kind(), node.text().as_str()));
+            }
+            return;
+        }
+
+        let mut embedded = false;
+        for child in node.children() {
+            let mode = match mode {
+                Mode::Verbatim => Mode::Verbatim,
+                _ if child.kind() == SyntaxKind::Equation => Mode::Verbatim,
+                Mode::Markup if embedded => Mode::Code,
+                Mode::Markup => Mode::Markup,
+                Mode::Code if node.kind() == SyntaxKind::ContentBlock => Mode::Markup,
+                Mode::Code => Mode::Code,
+            };
+            collect(child, mode, tokens);
+            embedded = child.kind() == SyntaxKind::Hashtag;
+        }
+    }
+
+    let mut tokens = vec![];
+    collect(root, Mode::Markup, &mut tokens);
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn test(text: &str, goal: &str) {
+        let formatted = format(text).unwrap();
+        assert_eq!(formatted, goal);
+        assert_eq!(format(&formatted).unwrap(), goal);
+    }
+
+    #[test]
+    fn test_format_code() {
+        test("#let x=(1,2)", "#let x = (1, 2)");
+        test("#f( a ,b:1 )", "#f(a, b: 1)");
+        test("#let f(x)=x+ -1", "#let f(x) = x + -1");
+        test("#{\nlet x = 1\n    x}", "#{\n  let x = 1\n  x\n}");
+        test("#set text( red )[Hi]", "#set text(red)[Hi]");
+    }
+
+    #[test]
+    fn test_format_markup() {
+        test("Hello  *world*  _!_", "Hello  *world*  _!_");
+        test("#[ a  b ] $ x  +y $", "#[ a  b ] $ x  +y $");
+    }
+
+    #[test]
+    fn test_format_errors() {
+        assert!(format("#let x = (").is_err());
+    }
+}
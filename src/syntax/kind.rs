@@ -230,12 +230,16 @@ pub enum SyntaxKind {
     WhileLoop,
     /// A for loop: `for x in y { z }`.
     ForLoop,
-    /// A for loop's destructuring pattern: `x` or `x, y`.
+    /// A for loop's destructuring pattern: `x`, `x, y` or `(x, y)`.
     ForPattern,
+    /// A destructuring pattern: `(x, ..rest, name: n)`.
+    Destructuring,
     /// A module import: `import a, b, c from "utils.typ"`.
     ModuleImport,
     /// Items to import from a module: `a, b, c`.
     ImportItems,
+    /// A renamed import item: `a as b`.
+    RenamedImportItem,
     /// A module include: `include "chapter1.typ"`.
     ModuleInclude,
     /// A break from a loop: `break`.
@@ -424,8 +428,10 @@ impl SyntaxKind {
             Self::WhileLoop => "while-loop expression",
             Self::ForLoop => "for-loop expression",
             Self::ForPattern => "for-loop destructuring pattern",
+            Self::Destructuring => "destructuring pattern",
             Self::ModuleImport => "`import` expression",
             Self::ImportItems => "import items",
+            Self::RenamedImportItem => "renamed import item",
             Self::ModuleInclude => "`include` expression",
             Self::LoopBreak => "`break` expression",
             Self::LoopContinue => "`continue` expression",
@@ -573,7 +573,7 @@ impl Lexer<'_> {
 }
 
 /// Try to parse an identifier into a keyword.
-fn keyword(ident: &str) -> Option<SyntaxKind> {
+pub(crate) fn keyword(ident: &str) -> Option<SyntaxKind> {
     Some(match ident {
         "none" => SyntaxKind::None,
         "auto" => SyntaxKind::Auto,
@@ -665,6 +665,18 @@ pub fn is_ident(string: &str) -> bool {
         .map_or(false, |c| is_id_start(c) && chars.all(is_id_continue))
 }
 
+/// Whether a string is a valid identifier in math.
+///
+/// Math identifiers cannot contain `_` and `-` and consist of at least two
+/// characters since single letters are just text.
+#[inline]
+pub(crate) fn is_math_ident(string: &str) -> bool {
+    let mut chars = string.chars();
+    chars.next().map_or(false, is_math_id_start)
+        && chars.clone().next().is_some()
+        && chars.all(is_math_id_continue)
+}
+
 /// Whether a character can start an identifier.
 #[inline]
 pub(crate) fn is_id_start(c: char) -> bool {
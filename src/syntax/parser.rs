@@ -828,6 +828,15 @@ fn let_binding(p: &mut Parser) {
     let m = p.marker();
     p.assert(SyntaxKind::Let);
 
+    if p.at(SyntaxKind::LeftParen) {
+        pattern(p);
+        if p.expect(SyntaxKind::Eq) {
+            code_expr(p);
+        }
+        p.wrap(m, SyntaxKind::LetBinding);
+        return;
+    }
+
     let m2 = p.marker();
     p.expect(SyntaxKind::Ident);
 
@@ -924,18 +933,77 @@ fn for_loop(p: &mut Parser) {
 
 fn for_pattern(p: &mut Parser) {
     let m = p.marker();
-    if p.expect(SyntaxKind::Ident) {
+    if p.at(SyntaxKind::LeftParen) {
+        pattern(p);
+        p.wrap(m, SyntaxKind::ForPattern);
+    } else if p.expect(SyntaxKind::Ident) {
         if p.eat_if(SyntaxKind::Comma) {
-            p.expect(SyntaxKind::Ident);
+            pattern(p);
         }
         p.wrap(m, SyntaxKind::ForPattern);
     }
 }
 
+fn pattern(p: &mut Parser) {
+    if p.at(SyntaxKind::LeftParen) {
+        let m = p.marker();
+        destructuring(p);
+        validate_destructuring(p, m);
+    } else {
+        p.expect(SyntaxKind::Ident);
+    }
+}
+
+fn destructuring(p: &mut Parser) {
+    let m = p.marker();
+    p.stop_at_newline(false);
+    p.assert(SyntaxKind::LeftParen);
+
+    while !p.current().is_terminator() {
+        let prev = p.prev_end();
+        destructuring_item(p);
+
+        if !p.progress(prev) {
+            p.unexpected();
+            continue;
+        }
+
+        if p.current().is_terminator() {
+            break;
+        }
+
+        p.expect(SyntaxKind::Comma);
+    }
+
+    p.expect(SyntaxKind::RightParen);
+    p.unstop();
+    p.wrap(m, SyntaxKind::Destructuring);
+}
+
+fn destructuring_item(p: &mut Parser) {
+    let m = p.marker();
+    if p.eat_if(SyntaxKind::Dots) {
+        p.expect(SyntaxKind::Ident);
+        p.wrap(m, SyntaxKind::Spread);
+    } else if p.at(SyntaxKind::LeftParen) {
+        destructuring(p);
+    } else if p.eat_if(SyntaxKind::Ident) && p.eat_if(SyntaxKind::Colon) {
+        if p.at(SyntaxKind::LeftParen) {
+            destructuring(p);
+        } else {
+            p.expect(SyntaxKind::Ident);
+        }
+        p.wrap(m, SyntaxKind::Named);
+    }
+}
+
 fn module_import(p: &mut Parser) {
     let m = p.marker();
     p.assert(SyntaxKind::Import);
     code_expr(p);
+    if p.eat_if(SyntaxKind::As) {
+        p.expect(SyntaxKind::Ident);
+    }
     if p.eat_if(SyntaxKind::Colon) && !p.eat_if(SyntaxKind::Star) {
         import_items(p);
     }
@@ -945,7 +1013,13 @@ fn module_import(p: &mut Parser) {
 fn import_items(p: &mut Parser) {
     let m = p.marker();
     while !p.eof() && !p.at(SyntaxKind::Semicolon) {
-        if !p.eat_if(SyntaxKind::Ident) {
+        let item = p.marker();
+        if p.eat_if(SyntaxKind::Ident) {
+            if p.eat_if(SyntaxKind::As) {
+                p.expect(SyntaxKind::Ident);
+                p.wrap(item, SyntaxKind::RenamedImportItem);
+            }
+        } else {
             p.unexpected();
         }
         if p.current().is_terminator() {
@@ -1064,6 +1138,58 @@ fn validate_params(p: &mut Parser, m: Marker) {
     }
 }
 
+fn validate_destructuring(p: &mut Parser, m: Marker) {
+    // Names must be unique across all nesting levels of the pattern.
+    let mut used = HashSet::new();
+    for child in p.post_process(m) {
+        validate_pattern(child, &mut used);
+    }
+}
+
+/// Check a (possibly nested) pattern for duplicate bindings and sinks.
+/// Returns whether an error was found.
+fn validate_pattern(node: &mut SyntaxNode, used: &mut HashSet<EcoString>) -> bool {
+    match node.kind() {
+        SyntaxKind::Ident => {
+            // The placeholder `_` may occur multiple times.
+            let duplicate = node.text() != "_" && !used.insert(node.text().clone());
+            if duplicate {
+                node.convert_to_error("duplicate binding");
+            }
+            duplicate
+        }
+        SyntaxKind::Destructuring => {
+            let mut sink = false;
+            let mut erroneous = false;
+            for child in node.children_mut() {
+                let kind = child.kind();
+                if kind == SyntaxKind::Spread && sink {
+                    child.convert_to_error("only one destructuring sink is allowed");
+                    erroneous = true;
+                    continue;
+                }
+
+                sink |= kind == SyntaxKind::Spread;
+                if matches!(kind, SyntaxKind::Named | SyntaxKind::Spread) {
+                    let Some(within) = child.children_mut().last_mut() else { continue };
+                    if validate_pattern(within, used) {
+                        child.make_erroneous();
+                        erroneous = true;
+                    }
+                } else {
+                    erroneous |= validate_pattern(child, used);
+                }
+            }
+
+            if erroneous {
+                node.make_erroneous();
+            }
+            erroneous
+        }
+        _ => false,
+    }
+}
+
 fn validate_args(p: &mut Parser, m: Marker) {
     let mut used = HashSet::new();
     for child in p.post_process(m) {
@@ -12,7 +12,7 @@ use super::ast::Markup;
 use super::reparser::reparse;
 use super::{is_newline, parse, LinkedNode, Span, SyntaxNode};
 use crate::diag::SourceResult;
-use crate::util::{PathExt, StrExt};
+use crate::util::{timing, PathExt, StrExt};
 
 /// A source file.
 ///
@@ -29,7 +29,10 @@ pub struct Source {
 impl Source {
     /// Create a new source file.
     pub fn new(id: SourceId, path: &Path, text: String) -> Self {
-        let mut root = parse(&text);
+        let mut root = {
+            let _scope = timing::scope("parse", || path.display().to_string().into());
+            parse(&text)
+        };
         root.numberize(id, Span::FULL).unwrap();
         Self {
             id,
@@ -0,0 +1,143 @@
+//! Recording of timings for compilation profiling.
+//!
+//! Recording is disabled by default, in which case all functions in this
+//! module are cheap no-ops.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use ecow::EcoString;
+
+/// Whether timings are currently recorded.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The timings recorded so far.
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Start recording timings, discarding any previously recorded ones.
+pub fn enable() {
+    *RECORDER.lock().unwrap() = Some(Recorder {
+        epoch: Instant::now(),
+        events: vec![],
+        memo: HashMap::new(),
+    });
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop recording and return the timings recorded since the last call to
+/// [`enable`].
+pub fn take() -> Option<Timings> {
+    ENABLED.store(false, Ordering::Relaxed);
+    let recorder = RECORDER.lock().unwrap().take()?;
+    let mut memo: Vec<_> = recorder.memo.into_iter().collect();
+    memo.sort_by_key(|&(func, _)| func);
+    Some(Timings { events: recorder.events, memo })
+}
+
+/// Record how long it takes until the returned scope is dropped.
+///
+/// The name is only computed if timings are recorded.
+pub fn scope<F>(cat: &'static str, name: F) -> Option<TimingScope>
+where
+    F: FnOnce() -> EcoString,
+{
+    ENABLED.load(Ordering::Relaxed).then(|| TimingScope {
+        name: name(),
+        cat,
+        start: Instant::now(),
+    })
+}
+
+/// Count a call to a memoized function.
+///
+/// Together with [`memo_miss`] in the function's body, this determines how
+/// often the function's result was reused.
+pub fn memo_call(func: &'static str) {
+    record_memo(func, |stats| stats.calls += 1);
+}
+
+/// Count an execution of a memoized function's body, i.e. a cache miss.
+pub fn memo_miss(func: &'static str) {
+    record_memo(func, |stats| stats.misses += 1);
+}
+
+/// Update the memoization statistics of a function.
+fn record_memo(func: &'static str, f: impl FnOnce(&mut MemoStats)) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    if let Some(recorder) = RECORDER.lock().unwrap().as_mut() {
+        f(recorder.memo.entry(func).or_default());
+    }
+}
+
+/// Timings recorded during compilation.
+#[derive(Debug, Clone)]
+pub struct Timings {
+    /// The recorded spans of time, ordered by their end.
+    pub events: Vec<TimingEvent>,
+    /// How often memoized functions were called and executed, sorted by the
+    /// functions' names.
+    pub memo: Vec<(&'static str, MemoStats)>,
+}
+
+/// A recorded span of time.
+#[derive(Debug, Clone)]
+pub struct TimingEvent {
+    /// What was done, e.g. the name of a layouted element.
+    pub name: EcoString,
+    /// The kind of work, e.g. `layout`.
+    pub cat: &'static str,
+    /// When the span started, relative to the start of recording.
+    pub start: Duration,
+    /// How long the span took.
+    pub duration: Duration,
+}
+
+/// How often a memoized function was called and executed.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct MemoStats {
+    /// How often the function was called.
+    pub calls: usize,
+    /// How often the function's body was executed.
+    pub misses: usize,
+}
+
+impl MemoStats {
+    /// How often a cached result was reused.
+    pub fn hits(&self) -> usize {
+        self.calls.saturating_sub(self.misses)
+    }
+}
+
+/// Records a span of time when dropped.
+#[must_use]
+pub struct TimingScope {
+    name: EcoString,
+    cat: &'static str,
+    start: Instant,
+}
+
+impl Drop for TimingScope {
+    fn drop(&mut self) {
+        let end = Instant::now();
+        if let Some(recorder) = RECORDER.lock().unwrap().as_mut() {
+            recorder.events.push(TimingEvent {
+                name: std::mem::take(&mut self.name),
+                cat: self.cat,
+                start: self.start.saturating_duration_since(recorder.epoch),
+                duration: end - self.start,
+            });
+        }
+    }
+}
+
+/// The state of recording.
+struct Recorder {
+    epoch: Instant,
+    events: Vec<TimingEvent>,
+    memo: HashMap<&'static str, MemoStats>,
+}
@@ -0,0 +1,7 @@
+// Relative paths resolve next to this file.
+#import "util.typ": double
+
+#let add(x, y) = x + y
+
+// Absolute paths resolve against the package's root.
+#let greeting = read("/greeting.txt")
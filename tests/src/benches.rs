@@ -3,7 +3,7 @@ use std::path::Path;
 use comemo::{Prehashed, Track, Tracked};
 use iai::{black_box, main, Iai};
 use typst::diag::{FileError, FileResult};
-use typst::eval::Library;
+use typst::eval::{Datetime, Library};
 use typst::font::{Font, FontBook};
 use typst::geom::Color;
 use typst::syntax::{Source, SourceId};
@@ -90,7 +90,7 @@ fn bench_compile(iai: &mut Iai) {
 
 fn bench_render(iai: &mut Iai) {
     let world = BenchWorld::new();
-    let document = typst::compile(&world).unwrap();
+    let (document, _) = typst::compile(&world).unwrap();
     iai.run(|| typst::export::render(&document.pages[0], 1.0, Color::WHITE))
 }
 
@@ -147,4 +147,8 @@ impl World for BenchWorld {
     fn file(&self, path: &Path) -> FileResult<Buffer> {
         Err(FileError::NotFound(path.into()))
     }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        None
+    }
 }
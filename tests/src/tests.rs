@@ -13,9 +13,9 @@ use once_cell::unsync::OnceCell;
 use tiny_skia as sk;
 use typst::diag::{bail, FileError, FileResult};
 use typst::doc::{Document, Frame, FrameItem, Meta};
-use typst::eval::{func, Library, Value};
+use typst::eval::{func, Datetime, Dict, Library, PackageSpec, Value};
 use typst::font::{Font, FontBook};
-use typst::geom::{Abs, Color, RgbaColor, Sides, Smart};
+use typst::geom::{Abs, Color, Point, RgbaColor, Sides, Size, Smart};
 use typst::syntax::{Source, SourceId, Span, SyntaxNode};
 use typst::util::{Buffer, PathExt};
 use typst::World;
@@ -28,8 +28,10 @@ const TYP_DIR: &str = "typ";
 const REF_DIR: &str = "ref";
 const PNG_DIR: &str = "png";
 const PDF_DIR: &str = "pdf";
+const SVG_DIR: &str = "svg";
 const FONT_DIR: &str = "../assets/fonts";
 const FILE_DIR: &str = "../assets/files";
+const PACKAGE_DIR: &str = "packages";
 
 fn main() {
     let args = Args::new(env::args().skip(1));
@@ -75,9 +77,17 @@ fn main() {
         let ref_path = Path::new(REF_DIR).join(path).with_extension("png");
         let pdf_path =
             args.pdf.then(|| Path::new(PDF_DIR).join(path).with_extension("pdf"));
+        let svg_path =
+            args.svg.then(|| Path::new(SVG_DIR).join(path).with_extension("svg"));
 
-        ok += test(&mut world, &src_path, &png_path, &ref_path, pdf_path.as_deref())
-            as usize;
+        ok += test(
+            &mut world,
+            &src_path,
+            &png_path,
+            &ref_path,
+            pdf_path.as_deref(),
+            svg_path.as_deref(),
+        ) as usize;
     }
 
     if len > 1 {
@@ -94,6 +104,7 @@ struct Args {
     filter: Vec<String>,
     exact: bool,
     pdf: bool,
+    svg: bool,
     print: PrintConfig,
 }
 
@@ -110,6 +121,7 @@ impl Args {
         let mut filter = Vec::new();
         let mut exact = false;
         let mut pdf = false;
+        let mut svg = false;
         let mut print = PrintConfig::default();
 
         for arg in args {
@@ -120,6 +132,8 @@ impl Args {
                 "--exact" => exact = true,
                 // Generate PDFs.
                 "--pdf" => pdf = true,
+                // Generate SVGs.
+                "--svg" => svg = true,
                 // Debug print the syntax trees.
                 "--syntax" => print.syntax = true,
                 // Debug print the model.
@@ -131,7 +145,7 @@ impl Args {
             }
         }
 
-        Self { filter, exact, pdf, print }
+        Self { filter, exact, pdf, svg, print }
     }
 
     fn matches(&self, path: &Path) -> bool {
@@ -173,7 +187,10 @@ fn library() -> Library {
         Value::None
     }
 
-    let mut lib = typst_library::build();
+    // Pass an input that tests can read back through `sys.inputs`.
+    let mut inputs = Dict::new();
+    inputs.insert("key".into(), Value::Str("value".into()));
+    let mut lib = typst_library::build_with_inputs(inputs);
 
     // Set page width to 120pt with 10pt margins, so that the inner page is
     // exactly 100pt wide. Page height is unbounded and font size is 10pt so
@@ -284,6 +301,34 @@ impl World for TestWorld {
             .get_or_init(|| read(path).map(Buffer::from))
             .clone()
     }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        Some(Datetime::from_ymd(1970, 1, 1).unwrap())
+    }
+
+    fn package(&self, spec: &PackageSpec) -> FileResult<PathBuf> {
+        let dir = Path::new(PACKAGE_DIR)
+            .join(spec.namespace.as_str())
+            .join(spec.name.as_str())
+            .join(spec.version.to_string());
+
+        if dir.is_dir() {
+            Ok(dir)
+        } else {
+            Err(FileError::PackageNotFound(spec.to_string().into()))
+        }
+    }
+
+    fn root_of(&self, id: SourceId) -> PathBuf {
+        // Package roots have the form `{namespace}/{name}/{version}`.
+        let path = self.source(id).path();
+        if let Ok(rest) = path.strip_prefix(PACKAGE_DIR) {
+            let root: PathBuf = rest.components().take(3).collect();
+            return Path::new(PACKAGE_DIR).join(root);
+        }
+
+        self.root().into()
+    }
 }
 
 impl TestWorld {
@@ -341,6 +386,7 @@ fn test(
     png_path: &Path,
     ref_path: &Path,
     pdf_path: Option<&Path>,
+    svg_path: Option<&Path>,
 ) -> bool {
     let name = src_path.strip_prefix(TYP_DIR).unwrap_or(src_path);
     println!("Testing {}", name.display());
@@ -387,6 +433,12 @@ fn test(
             fs::write(pdf_path, pdf_data).unwrap();
         }
 
+        if let Some(svg_path) = svg_path {
+            let svg_data = typst::export::svg(&merge(&document.pages));
+            fs::create_dir_all(&svg_path.parent().unwrap()).unwrap();
+            fs::write(svg_path, svg_data).unwrap();
+        }
+
         if world.print.frames {
             for frame in &document.pages {
                 println!("Frame:\n{:#?}\n", frame);
@@ -442,11 +494,12 @@ fn test_part(
         println!("Syntax Tree:\n{:#?}\n", source.root())
     }
 
-    let (local_compare_ref, mut ref_errors) = parse_metadata(&source);
+    let (local_compare_ref, mut ref_errors, mut ref_warnings) = parse_metadata(&source);
     let compare_ref = local_compare_ref.unwrap_or(compare_ref);
 
     ok &= test_spans(source.root());
     ok &= test_reparse(world.source(id).text(), i, rng);
+    ok &= test_format(world.source(id).text(), i);
 
     if world.print.model {
         let world = (world as &dyn World).track();
@@ -457,9 +510,9 @@ fn test_part(
         println!("Model:\n{:#?}\n", module.content());
     }
 
-    let (mut frames, errors) = match typst::compile(world) {
-        Ok(document) => (document.pages, vec![]),
-        Err(errors) => (vec![], *errors),
+    let (mut frames, errors, warnings) = match typst::compile(world) {
+        Ok((document, warnings)) => (document.pages, vec![], warnings),
+        Err(errors) => (vec![], *errors, vec![]),
     };
 
     // Don't retain frames if we don't wanna compare with reference images.
@@ -481,29 +534,37 @@ fn test_part(
     if errors != ref_errors {
         println!("  Subtest {i} does not match expected errors. ❌");
         ok = false;
+        print_mismatches(world.source(id), line, "Error", &errors, &ref_errors);
+    }
 
-        let source = world.source(id);
-        for error in errors.iter() {
-            if !ref_errors.contains(error) {
-                print!("    Not annotated | ");
-                print_error(&source, line, error);
-            }
-        }
+    // Warnings are only checked for subtests that annotate some, so that
+    // tests don't need to care about unrelated warnings.
+    if !ref_warnings.is_empty() {
+        let mut warnings: Vec<_> = warnings
+            .into_iter()
+            .filter(|warning| warning.span.source() == id)
+            .map(|warning| (warning.range(world), warning.message.to_string()))
+            .collect();
 
-        for error in ref_errors.iter() {
-            if !errors.contains(error) {
-                print!("    Not emitted   | ");
-                print_error(&source, line, error);
-            }
+        warnings.sort_by_key(|warning| warning.0.start);
+        ref_warnings.sort_by_key(|warning| warning.0.start);
+
+        if warnings != ref_warnings {
+            println!("  Subtest {i} does not match expected warnings. ❌");
+            ok = false;
+            print_mismatches(world.source(id), line, "Warning", &warnings, &ref_warnings);
         }
     }
 
     (ok, compare_ref, frames)
 }
 
-fn parse_metadata(source: &Source) -> (Option<bool>, Vec<(Range<usize>, String)>) {
+type Annotations = Vec<(Range<usize>, String)>;
+
+fn parse_metadata(source: &Source) -> (Option<bool>, Annotations, Annotations) {
     let mut compare_ref = None;
     let mut errors = vec![];
+    let mut warnings = vec![];
 
     let lines: Vec<_> = source.text().lines().map(str::trim).collect();
     for (i, line) in lines.iter().enumerate() {
@@ -530,24 +591,88 @@ fn parse_metadata(source: &Source) -> (Option<bool>, Vec<(Range<usize>, String)>
             source.line_column_to_byte(line, column).unwrap()
         };
 
-        let Some(rest) = line.strip_prefix("// Error: ") else { continue };
+        let (rest, annotations) = if let Some(rest) = line.strip_prefix("// Error: ") {
+            (rest, &mut errors)
+        } else if let Some(rest) = line.strip_prefix("// Warning: ") {
+            (rest, &mut warnings)
+        } else {
+            continue;
+        };
+
         let mut s = Scanner::new(rest);
         let start = pos(&mut s);
         let end = if s.eat_if('-') { pos(&mut s) } else { start };
         let range = start..end;
 
-        errors.push((range, s.after().trim().to_string()));
+        annotations.push((range, s.after().trim().to_string()));
     }
 
-    (compare_ref, errors)
+    (compare_ref, errors, warnings)
 }
 
-fn print_error(source: &Source, line: usize, (range, message): &(Range<usize>, String)) {
+fn print_mismatches(
+    source: &Source,
+    line: usize,
+    kind: &str,
+    emitted: &Annotations,
+    expected: &Annotations,
+) {
+    for annotation in emitted.iter() {
+        if !expected.contains(annotation) {
+            print!("    Not annotated | ");
+            print_error(source, line, kind, annotation);
+        }
+    }
+
+    for annotation in expected.iter() {
+        if !emitted.contains(annotation) {
+            print!("    Not emitted   | ");
+            print_error(source, line, kind, annotation);
+        }
+    }
+}
+
+fn print_error(
+    source: &Source,
+    line: usize,
+    kind: &str,
+    (range, message): &(Range<usize>, String),
+) {
     let start_line = 1 + line + source.byte_to_line(range.start).unwrap();
     let start_col = 1 + source.byte_to_column(range.start).unwrap();
     let end_line = 1 + line + source.byte_to_line(range.end).unwrap();
     let end_col = 1 + source.byte_to_column(range.end).unwrap();
-    println!("Error: {start_line}:{start_col}-{end_line}:{end_col}: {message}");
+    println!("{kind}: {start_line}:{start_col}-{end_line}:{end_col}: {message}");
+}
+
+/// Format the source file and test whether formatting succeeds and is
+/// idempotent. Files with syntax errors are skipped.
+fn test_format(text: &str, i: usize) -> bool {
+    if typst::syntax::parse(text).erroneous() {
+        return true;
+    }
+
+    let formatted = match typst::syntax::format(text) {
+        Ok(formatted) => formatted,
+        Err(error) => {
+            println!("    Subtest {i} cannot be formatted: {error} ❌");
+            return false;
+        }
+    };
+
+    match typst::syntax::format(&formatted) {
+        Ok(again) if again == formatted => true,
+        Ok(again) => {
+            println!("    Subtest {i} formatting is not idempotent ❌\n");
+            println!("    Formatted once:\n{formatted:?}\n");
+            println!("    Formatted twice:\n{again:?}");
+            false
+        }
+        Err(error) => {
+            println!("    Subtest {i} cannot be formatted after formatting: {error} ❌");
+            false
+        }
+    }
 }
 
 /// Pseudorandomly edit the source file and test whether a reparse produces the
@@ -730,6 +855,22 @@ fn render(frames: &[Frame]) -> sk::Pixmap {
     canvas
 }
 
+/// Stack all frames into one frame with padding in between.
+fn merge(frames: &[Frame]) -> Frame {
+    let pad = Abs::pt(5.0);
+    let width = frames.iter().map(Frame::width).max().unwrap_or_default() + pad * 2.0;
+    let height = pad + frames.iter().map(|frame| frame.height() + pad).sum::<Abs>();
+
+    let mut merged = Frame::new(Size::new(width, height));
+    let mut y = pad;
+    for frame in frames {
+        merged.push_frame(Point::new(pad, y), frame.clone());
+        y += frame.height() + pad;
+    }
+
+    merged
+}
+
 /// Draw extra boxes for links so we can see whether they are there.
 fn render_links(canvas: &mut sk::Pixmap, ts: sk::Transform, frame: &Frame) {
     for (pos, item) in frame.items() {
@@ -201,6 +201,68 @@
 // Error: 2-26 cannot order content and content
 #([Hi], [There]).sorted()
 
+---
+// Test the `sorted` method with a key function.
+#test((3, -1, 2).sorted(key: x => calc.abs(x)), (-1, 2, 3))
+#test(("ab", "c", "def").sorted(key: s => s.len()), ("c", "ab", "def"))
+#test(((2, "b"), (1, "a")).sorted(key: p => p.first()), ((1, "a"), (2, "b")))
+
+---
+// Error: 2-31 cannot order content and content
+#(1, 2).sorted(key: _ => [Hi])
+
+---
+// Test the `zip` and `enumerate` methods.
+#test((1, 2, 3).zip(("a", "b", "c")), ((1, "a"), (2, "b"), (3, "c")))
+#test((1, 2, 3).zip(("a",)), ((1, "a"),))
+#test(().zip((1, 2)), ())
+#test(("a", "b").enumerate(), ((0, "a"), (1, "b")))
+#test(().enumerate(), ())
+
+---
+// Test the `sum` and `product` methods.
+#test((1, 2, 3).sum(), 6)
+#test((1, 2.5).sum(), 3.5)
+#test(("a", "b").sum(), "ab")
+#test(().sum(default: 0), 0)
+#test((2, 3, 4).product(), 24)
+#test(().product(default: 1), 1)
+
+---
+// Error: 2-10 cannot calculate sum of empty array with no default
+#().sum()
+
+---
+// Error: 2-14 cannot calculate product of empty array with no default
+#().product()
+
+---
+// Error: 2-16 cannot add integer and string
+#(1, "a").sum()
+
+---
+// Test the `dedup` and `intersperse` methods.
+#test((1, 2, 1, 3, 2).dedup(), (1, 2, 3))
+#test(("a", "a", 1, 1.0, "1").dedup(), ("a", 1, "1"))
+#test((1, 2, 3).intersperse(0), (1, 0, 2, 0, 3))
+#test((1,).intersperse(0), (1,))
+#test(().intersperse(0), ())
+
+---
+// Test the `chunks` and `windows` methods.
+#test((1, 2, 3, 4, 5).chunks(2), ((1, 2), (3, 4), (5,)))
+#test((1, 2).chunks(5), ((1, 2),))
+#test((1, 2, 3).windows(2), ((1, 2), (2, 3)))
+#test((1, 2).windows(3), ())
+
+---
+// Error: 19-20 number must be positive
+#(1, 2, 3).chunks(0)
+
+---
+// Error: 20-22 number must be positive
+#(1, 2, 3).windows(-1)
+
 ---
 // Error: 2-18 array index out of bounds (index: -4, len: 3)
 #(1, 2, 3).at(-4)
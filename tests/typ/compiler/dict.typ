@@ -55,6 +55,27 @@
 #test("c" in dict, false)
 #test(dict, (a: 3, b: 1))
 
+---
+// Test the `at` method with a default.
+#let dict = (a: 1)
+#test(dict.at("a", default: 2), 1)
+#test(dict.at("b", default: 2), 2)
+#test(dict.at("b", default: none), none)
+
+---
+// Test the `filter`, `map` and `merge` methods.
+#let dict = (a: 1, b: 2, c: 3)
+#test(dict.filter((k, v) => v > 1), (b: 2, c: 3))
+#test(dict.filter((k, v) => k == "a"), (a: 1))
+#test(dict.map((k, v) => k + str(v)), (a: "a1", b: "b2", c: "c3"))
+#test((:).map((k, v) => v), (:))
+#test(dict.merge((c: 4, d: 5)), (a: 1, b: 2, c: 4, d: 5))
+#test(dict.merge((:)), dict)
+
+---
+// Error: 16-22 expected boolean, found integer
+#(a: 1).filter((k, v) => v)
+
 ---
 // Error: 24-29 duplicate key
 #(first: 1, second: 2, first: 3)
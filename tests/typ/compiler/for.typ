@@ -78,6 +78,48 @@
 #test(for v in "" [], none)
 #test(type(for v in "1" []), "content")
 
+---
+// Ref: false
+// Destructuring array elements.
+#let sum = 0
+#for (a, b) in ((1, 2), (3, 4)) {
+  sum += a * b
+}
+#test(sum, 14)
+
+// Destructuring with the index.
+#let out = ()
+#for i, (a, ..rest) in ((1, 2, 3), (4,)) {
+  out.push((i, a, rest))
+}
+#test(out, ((0, 1, (2, 3)), (1, 4, ())))
+
+// Destructuring dictionary pairs.
+#let pairs = ()
+#for (k, v) in (a: 1, b: 2) {
+  pairs.push(k + str(v))
+}
+#test(pairs, ("a1", "b2"))
+
+// Nested destructuring.
+#let keys = ()
+#for ((k, v), i) in ((("a", 1), 0), (("b", 2), 1)) {
+  keys.push(k * (v + i))
+}
+#test(keys, ("a", "bbb"))
+
+// Destructuring a variable.
+#let points = ((1, 2), (3, 4))
+#let xs = ()
+#for (x, y) in points {
+  xs.push(x)
+}
+#test(xs, (1, 3))
+
+---
+// Error: 6-12 not enough elements to destructure (expected 2, found 1)
+#for (a, b) in ((1,),) {}
+
 ---
 // Uniterable expression.
 // Error: 11-15 cannot loop over boolean
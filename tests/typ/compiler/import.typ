@@ -41,6 +41,38 @@
 #test(module.item(1, 2), 3)
 #test(module.push(2), 3)
 
+---
+// A renamed module import.
+#import "module.typ" as other
+#test(other.b, 1)
+#test(other.item(1, 2), 3)
+
+// Renamed items.
+#import "module.typ": item as add, b as one, c
+#test(add(1, 2), 3)
+#test(one, 1)
+#test(c, 2)
+
+// Both at once.
+#import "module.typ" as mod: d as three
+#test(mod.d, 3)
+#test(three, 3)
+
+---
+// Importing from nested modules.
+#import calc: pow
+#test(pow(2, 3), 8)
+
+#import "modules/util.typ"
+#import util.colors: *
+#test(primary, blue)
+
+#import util.colors: secondary as accent
+#test(accent, red)
+
+#import util.colors
+#test(colors.primary, blue)
+
 ---
 // Edge case for module access that isn't fixed.
 #import "module.typ"
@@ -77,6 +109,19 @@
 // Error: 23-35 unresolved import
 #import "module.typ": non_existing
 
+---
+// Unresolved renamed import.
+// Error: 23-35 unresolved import
+#import "module.typ": non_existing as x
+
+---
+// Error: 9-13 expected path or module, found integer
+#import 1234 as x
+
+---
+// Error: 24 expected identifier
+#import "module.typ" as
+
 ---
 // Cyclic import of this very file.
 // Error: 9-23 cyclic import
@@ -128,3 +173,7 @@ This is never reached.
 ---
 // Error: 14 expected comma
 #import "": a b
+
+---
+// Error: 27 expected identifier
+#import "module.typ": a as
@@ -32,6 +32,95 @@ Three
 #test(v2, 2)
 #test(v3, 3)
 
+---
+// Ref: false
+// Simple destructuring.
+#let (a, b) = (1, 2)
+#test(a, 1)
+#test(b, 2)
+
+// Destructuring with a sink.
+#let (first, ..rest) = (1, 2, 3, 4)
+#test(first, 1)
+#test(rest, (2, 3, 4))
+
+#let (a, ..b, c) = (1, 2)
+#test(a, 1)
+#test(b, ())
+#test(c, 2)
+
+// Nested destructuring.
+#let ((a, b), c) = ((1, 2), 3)
+#test((a, b, c), (1, 2, 3))
+
+// Destructuring a variable.
+#let p = (1, 2)
+#let (x, y) = p
+#test((x, y), (1, 2))
+
+#let d = (name: "Typst", age: 5)
+#let (name, ..rest) = d
+#test(name, "Typst")
+#test(rest, (age: 5))
+
+// The placeholder can occur multiple times.
+#let (_, _, z) = (1, 2, 3)
+#test(z, 3)
+
+---
+// Ref: false
+// Destructuring dictionaries.
+#let (name, age) = (name: "Typst", age: 5)
+#test(name, "Typst")
+#test(age, 5)
+
+#let (name: n, ..other) = (name: "Typst", age: 5, lang: "rust")
+#test(n, "Typst")
+#test(other, (age: 5, lang: "rust"))
+
+#let (point: (x, y)) = (point: (1, 2))
+#test((x, y), (1, 2))
+
+---
+// Error: 6-12 too many elements to destructure (expected 2, found 3)
+#let (a, b) = (1, 2, 3)
+
+---
+// Error: 6-15 not enough elements to destructure (expected 3, found 2)
+#let (a, b, c) = (1, 2)
+
+---
+// Error: 6-20 not enough elements to destructure (expected at least 3, found 2)
+#let (a, b, ..c, d) = (1, 2)
+
+---
+// Error: 7-11 cannot destructure named elements from an array
+#let (a: b) = (1, 2)
+
+---
+// Error: 6-12 cannot destructure integer
+#let (a, b) = 1
+
+---
+// Error: 7-8 dictionary does not contain key "b"
+#let (b,) = (a: 1)
+
+---
+// Error: 10-11 duplicate binding
+#let (a, a) = (1, 2)
+
+---
+// Error: 15-16 duplicate binding
+#let ((x, y), x) = ((1, 2), 3)
+
+---
+// Error: 22-23 duplicate binding
+#let (point: (x, y), y) = (point: (1, 2), y: 3)
+
+---
+// Error: 12-15 only one destructuring sink is allowed
+#let (..a, ..b) = (1, 2)
+
 ---
 // Error: 5 expected identifier
 #let
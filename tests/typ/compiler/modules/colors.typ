@@ -0,0 +1,5 @@
+// A module that is imported by `util.typ`.
+// Ref: false
+
+#let primary = blue
+#let secondary = red
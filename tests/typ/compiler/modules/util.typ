@@ -0,0 +1,5 @@
+// A module with a nested module for import tests.
+// Ref: false
+
+#import "colors.typ"
+#let double(x) = 2 * x
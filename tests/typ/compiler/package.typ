@@ -0,0 +1,37 @@
+// Test package imports.
+// Ref: false
+
+---
+#import "@test/adder:0.1.0": add, double
+#test(add(1, 2), 3)
+#test(double(2), 4)
+
+---
+// A package module is named after the package.
+#import "@test/adder:0.1.0"
+#test(adder.add(2, 3), 5)
+
+---
+// Absolute paths in a package are relative to the package's root.
+#import "@test/adder:0.1.0": greeting
+#test(greeting, "Hello from adder!")
+
+---
+// Error: 9-22 package specification is missing version
+#import "@test/adder": *
+
+---
+// Error: 9-26 version number is missing patch version
+#import "@test/adder:0.1": *
+
+---
+// Error: 9-30 package not found (@test/missing:0.1.0)
+#import "@test/missing:0.1.0": *
+
+---
+// Error: 9-29 package manifest contains entrypoint outside of the package: `../../adder/0.1.0/src/lib.typ`
+#import "@test/escape:0.1.0": *
+
+---
+// Error: 9-29 package manifest contains entrypoint outside of the package: `/src/lib.typ`
+#import "@test/escape:0.2.0": *
@@ -0,0 +1,47 @@
+// Test the bytes type.
+// Ref: false
+
+---
+// Test construction and conversion.
+#let data = bytes("Hello")
+#test(type(data), "bytes")
+#test(data.len(), 5)
+#test(array(data), (72, 101, 108, 108, 111))
+#test(bytes((72, 101, 108, 108, 111)), data)
+#test(bytes(data), data)
+#test(str(data), "Hello")
+#test(str(bytes("😃")), "😃")
+#test(bytes(()).len(), 0)
+#test(repr(data), "bytes(5)")
+
+---
+// Test indexing and slicing.
+#let data = bytes((1, 2, 3, 4, 5))
+#test(data.at(0), 1)
+#test(data.at(-1), 5)
+#test(array(data.slice(1, 3)), (2, 3))
+#test(array(data.slice(-2)), (4, 5))
+#test(array(data.slice(1, count: 2)), (2, 3))
+#test(data.slice(3, 1).len(), 0)
+
+---
+// Test concatenation and comparison.
+#test(bytes("ab") + bytes("cd"), bytes("abcd"))
+#test(bytes("a") == bytes((97,)), true)
+#test(bytes("a") != bytes("b"), true)
+
+---
+// Error: 2-20 byte index out of bounds (index: 3, len: 3)
+#bytes("abc").at(3)
+
+---
+// Error: 2-26 byte index out of bounds (index: 5, len: 3)
+#bytes("abc").slice(1, 5)
+
+---
+// Error: 8-16 array must only contain integers between 0 and 255
+#bytes((1, 256))
+
+---
+// Error: 6-21 bytes are not valid utf-8
+#str(bytes((255, 0)))
@@ -69,6 +69,164 @@
 // Error: 16-19 divisor must not be zero
 #calc.mod(3.0, 0.0)
 
+---
+// Test the `rem` and `quo` functions.
+#test(calc.rem(20, 6), 2)
+#test(calc.rem(-7, 3), -1)
+#test(calc.rem(7, -3), 1)
+#test(calc.rem(1.75, 0.5), 0.25)
+#test(calc.quo(14, 5), 2)
+#test(calc.quo(-7, 2), -4)
+#test(calc.quo(7, -2), -4)
+#test(calc.quo(-6, 3), -2)
+#test(calc.quo(3.0, 0.5), 6)
+#test(calc.mod(-9223372036854775807 - 1, -1), 0)
+
+---
+// Error: 14-15 divisor must not be zero
+#calc.quo(5, 0)
+
+---
+// Error: 10-40 the result is too large
+#calc.quo(-9223372036854775807 - 1, -1)
+
+---
+// Error: 10-20 the result is too large
+#calc.quo(1e300, 1)
+
+---
+// Error: 10-23 the result is not a number
+#calc.quo(calc.nan, 1)
+
+---
+// Test the `trunc`, `fract`, `exp` and `ln` functions.
+#test(calc.trunc(3), 3)
+#test(calc.trunc(-3.7), -3)
+#test(calc.trunc(15.9), 15)
+#test(calc.fract(3), 0)
+#test(calc.fract(2.5), 0.5)
+#test(calc.fract(-3.25), -0.25)
+#test(calc.exp(0), 1.0)
+#test(calc.ln(1), 0.0)
+#test(calc.round(calc.ln(calc.exp(2.5)), digits: 5), 2.5)
+#test(calc.round(calc.tau, digits: 2), 6.28)
+#test(calc.atan2(1, 1), calc.atan(1))
+#test(calc.atan2(-1, 0) > 179deg, true)
+#test(calc.atan2(0, -1) < -89deg, true)
+
+---
+// Error: 10-11 value must be strictly positive
+#calc.ln(0)
+
+---
+// Error: 12-22 the result is too large
+#calc.trunc(calc.inf)
+
+---
+// Error: 12-23 the result is too large
+#calc.trunc(-calc.inf)
+
+---
+// Error: 12-22 the result is not a number
+#calc.trunc(calc.nan)
+
+---
+// Test the number theory and combinatorics functions.
+#test(calc.gcd(7, 42), 7)
+#test(calc.gcd(-12, 18), 6)
+#test(calc.gcd(0, 5), 5)
+#test(calc.gcd(0, 0), 0)
+#test(calc.lcm(4, 6), 12)
+#test(calc.lcm(-4, 6), 12)
+#test(calc.lcm(0, 6), 0)
+#test(calc.fact(0), 1)
+#test(calc.fact(5), 120)
+#test(calc.fact(20), 2432902008176640000)
+#test(calc.perm(10, 5), 30240)
+#test(calc.perm(5, 0), 1)
+#test(calc.perm(3, 5), 0)
+#test(calc.binom(10, 5), 252)
+#test(calc.binom(5, 0), 1)
+#test(calc.binom(3, 5), 0)
+#test(calc.binom(66, 33), 7219428434016265740)
+
+---
+// Error: 11-15 the result is too large
+#calc.fact(21)
+
+---
+// Error: 12-20 the result is too large
+#calc.binom(68, 34)
+
+---
+// Error: 10-34 the result is too large
+#calc.lcm(9223372036854775807, 2)
+
+---
+// Error: 10-17 the result is too large
+#calc.pow(2, 63)
+
+---
+// Error: 12-14 number must not be negative
+#calc.fact(-1)
+
+---
+// Error: 15-17 number must not be negative
+#calc.perm(5, -2)
+
+---
+// Error: 11-35 the result is too large
+#calc.abs(-9223372036854775807 - 1)
+
+---
+// Test the `rng` function.
+#let rng = calc.rng(seed: 42)
+#let (a, next) = rng.int(1, 7)
+#let (b, _) = rng.int(1, 7)
+#test(a, b)
+#test(type(next), "rng")
+#test(rng == calc.rng(seed: 42), true)
+#test(next == rng, false)
+#test(repr(rng), "rng(..)")
+
+---
+// Test that the generated values stay within bounds.
+#{
+  let rng = calc.rng(seed: 1)
+  for _ in range(100) {
+    let (n, next) = rng.int(-3, 3)
+    test(n >= -3 and n < 3, true)
+    let (f, next) = next.float()
+    test(f >= 0.0 and f < 1.0, true)
+    rng = next
+  }
+}
+
+---
+// Test the `choice` and `shuffle` methods.
+#let rng = calc.rng(seed: 7)
+#let (item, rng) = rng.choice(("a", "b", "c"))
+#test(item in ("a", "b", "c"), true)
+#let (shuffled, rng) = rng.shuffle(range(10))
+#test(shuffled.len(), 10)
+#test(shuffled.sorted(), range(10))
+#test(rng.shuffle(()).first(), ())
+
+---
+// Error: 10-12 missing argument: seed
+#calc.rng()
+
+---
+// Error: 2-29 end must be greater than start
+#calc.rng(seed: 1).int(3, 3)
+
+---
+// Error: 2-30 cannot choose from an empty array
+#calc.rng(seed: 1).choice(())
+
+---
+// Error: 2-26 type rng has no method `pick`
+#calc.rng(seed: 1).pick()
 ---
 // Test the `min` and `max` functions.
 #test(calc.min(2, -4), -4)
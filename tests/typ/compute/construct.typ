@@ -64,7 +64,7 @@
 #test(str(10 / 3).len() > 10, true)
 
 ---
-// Error: 6-8 expected integer, float, label, or string, found content
+// Error: 6-8 expected integer, float, label, string, or bytes, found content
 #str([])
 
 ---
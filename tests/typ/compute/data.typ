@@ -6,6 +6,14 @@
 #let data = read("/hello.txt")
 #test(data, "Hello, world!")
 
+---
+// Test reading raw bytes.
+#let data = read("/hello.txt", encoding: none)
+#test(type(data), "bytes")
+#test(data.len(), 13)
+#test(data.at(0), 72)
+#test(str(data), "Hello, world!")
+
 ---
 // Error: 18-32 file not found (searched at /missing.txt)
 #let data = read("/missing.txt")
@@ -37,6 +45,11 @@
 #test(data.at(0).name, "Debby")
 #test(data.at(2).weight, 150)
 
+---
+// Test reading JSON data from bytes.
+#let data = json(bytes("{\"a\": [1, 2]}"))
+#test(data, (a: (1, 2)))
+
 ---
 // Error: 7-18 failed to parse json file: syntax error in line 3
 #json("/bad.json")
@@ -69,3 +82,7 @@
 ---
 // Error: 6-16 failed to parse xml file: found closing tag 'data' instead of 'hello' in line 3
 #xml("/bad.xml")
+
+---
+// Error: 42-49 expected "utf8" or none
+#let data = read("/hello.txt", encoding: "utf16")
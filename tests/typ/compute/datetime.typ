@@ -0,0 +1,105 @@
+// Test datetimes and durations.
+// Ref: false
+
+---
+// Test accessing the components.
+#let d = datetime(year: 2023, month: 5, day: 1)
+#test(d.year(), 2023)
+#test(d.month(), 5)
+#test(d.day(), 1)
+#test(d.weekday(), 1)
+#test(d.hour(), none)
+
+#let t = datetime(hour: 14, minute: 3, second: 9)
+#test(t.year(), none)
+#test((t.hour(), t.minute(), t.second()), (14, 3, 9))
+
+---
+// Test displaying datetimes.
+#let d = datetime(year: 2023, month: 5, day: 1, hour: 14, minute: 3, second: 9)
+#test(d.display(), "2023-05-01 14:03:09")
+#test(d.display("[day]. [month repr:long] [year]"), "01. May 2023")
+#test(d.display("[weekday], [hour repr:12]:[minute] [period]"), "Monday, 02:03 PM")
+#test(d.display("[[[year repr:last_two]]"), "[23]")
+#test(datetime(year: 2023, month: 5, day: 1).display(), "2023-05-01")
+#test(datetime(hour: 1, minute: 2, second: 3).display(), "01:02:03")
+#test(repr(datetime(year: 2023, month: 5, day: 1)), "datetime(year: 2023, month: 5, day: 1)")
+
+---
+// Test comparing datetimes.
+#let a = datetime(year: 2023, month: 2, day: 28)
+#let b = datetime(year: 2024, month: 2, day: 28)
+#test(a < b, true)
+#test(a == datetime(year: 2023, month: 2, day: 28), true)
+#test(a == datetime(hour: 0, minute: 0, second: 0), false)
+
+---
+// Test datetime and duration arithmetic.
+#let a = datetime(year: 2023, month: 2, day: 28)
+#let b = datetime(year: 2024, month: 2, day: 28)
+#test(a + duration(days: 1), datetime(year: 2023, month: 3, day: 1))
+#test(b + duration(days: 1), datetime(year: 2024, month: 2, day: 29))
+#test(a - duration(weeks: 9), datetime(year: 2022, month: 12, day: 27))
+#test((b - a).days(), 365.0)
+#test(datetime(hour: 23, minute: 0, second: 0) + duration(hours: 2), datetime(hour: 1, minute: 0, second: 0))
+#test(duration(hours: 1) * 3, duration(minutes: 180))
+#test(duration(days: 1) / duration(hours: 6), 4.0)
+#test(-duration(seconds: 90) + duration(minutes: 2), duration(seconds: 30))
+#test(repr(duration(days: 1, hours: 2)), "duration(days: 1, hours: 2)")
+
+---
+// The test world always returns the same date.
+#test(datetime.today(), datetime(year: 1970, month: 1, day: 1))
+#test(datetime.today().display("[weekday]"), "Thursday")
+
+---
+// Error: 10-32 date is incomplete
+#datetime(year: 2023, month: 5)
+
+---
+// Error: 10-41 date is invalid
+#datetime(year: 2023, month: 2, day: 29)
+
+---
+// Error: 10-42 time is invalid
+#datetime(hour: 24, minute: 0, second: 0)
+
+---
+// Error: 10-12 at least one of date or time must be fully specified
+#datetime()
+
+---
+// Error: 3-62 cannot add a duration with a time component to a date
+#(datetime(year: 2023, month: 5, day: 1) + duration(hours: 1))
+
+---
+// Error: 3-83 cannot subtract date from time
+#(datetime(hour: 1, minute: 0, second: 0) - datetime(year: 2023, month: 5, day: 1))
+
+---
+// Error: 3-64 duration is too long
+#(duration(seconds: 9223372036854775807) + duration(seconds: 1))
+
+---
+// Error: 3-65 duration is too long
+#(duration(seconds: -9223372036854775807) - duration(seconds: 2))
+
+---
+// Error: 3-47 duration is too long
+#(-duration(seconds: -9223372036854775807 - 1))
+
+---
+// Error: 2-58 cannot display the hour of a date
+#datetime(year: 2023, month: 5, day: 1).display("[hour]")
+
+---
+// Error: 2-69 invalid value `full` for modifier `repr`
+#datetime(year: 2023, month: 5, day: 1).display("[month repr:full]")
+
+---
+// Error: 2-58 unknown component `days`
+#datetime(year: 2023, month: 5, day: 1).display("[days]")
+
+---
+// Error: 11-20 function `datetime` does not contain `yesterday`
+#datetime.yesterday
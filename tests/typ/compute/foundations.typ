@@ -94,3 +94,9 @@ _No relative giraffe!_
 ---
 // Error: 7-12 expected semicolon or line break
 #eval("1 2")
+
+---
+// Test the inputs passed by the test runner.
+#test(type(sys.inputs), "dictionary")
+#test(sys.inputs, (key: "value"))
+#test(sys.inputs.at("key"), "value")
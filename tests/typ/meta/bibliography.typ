@@ -27,3 +27,10 @@ As described by @netwok],
 the net-work is a creature of its own.
 This is close to piratery! @arrgh
 And quark! @quark
+
+---
+// Ref: false
+#set document(draft: true)
+// Warning: 2-15 bibliography does not contain the key nokey
+#cite("nokey")
+#bibliography("/works.bib")
@@ -19,3 +19,28 @@ As seen in @intro, we proceed.
 
 // Error: 1-5 label occurs multiple times in the document
 @foo
+
+---
+// Ref: false
+#set document(draft: true)
+
+// Warning: 1-5 label `<foo>` does not exist in the document
+@foo
+
+---
+// Ref: false
+// Warning: 6-11 label `<foo>` is never referenced
+*Hi* <foo>
+
+---
+// Ref: false
+// Labels used in selectors, references and the label function are not unused.
+#show <shown>: set text(red)
+#locate(loc => query(<queried>, loc))
+#let name = label("constructed")
+*Hi* <shown>
+*Hi* <queried>
+*Hi* <constructed>
+#set heading(numbering: "1.")
+= Intro <referenced>
+@referenced
@@ -37,3 +37,10 @@ Was: #locate(location => {
 #trait[Adventure]
 #trait[Fear]
 #trait[Anger]
+
+---
+// Test that an introspection that never stabilizes is reported.
+// Ref: false
+#let s = state("x", 0)
+// Warning: 2-43 introspection did not converge within 5 layout attempts
+#locate(loc => s.update(s.final(loc) + 1))
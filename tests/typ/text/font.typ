@@ -58,3 +58,21 @@ Emoji: 🐪, 🌋, 🏞
 ---
 // Error: 11-31 unexpected argument
 #set text(something: "invalid")
+
+---
+// Ref: false
+// Warning: 13-26 unknown font family: nonexistent
+#text(font: "Nonexistent")[Hi]
+#text(font: ("Nonexistent", "Linux Libertine"))[Hi]
+
+// Warning: 13-39 unknown font families: nonexistent, unknown
+#text(font: ("Nonexistent", "Unknown"))[There]
+
+---
+// Ref: false
+// The warning is reported once for the set rule, not for every paragraph.
+// Warning: 17-23 unknown font family: typo
+#set text(font: "Typo")
+First
+
+Second
@@ -53,10 +53,19 @@ A #box(image("/tiger.jpg", height: 1cm, width: 80%)) B
 // Error: 8-29 file not found (searched at typ/visualize/path/does/not/exist)
 #image("path/does/not/exist")
 
+---
+// Test loading an image from raw bytes.
+// Ref: false
+#image(read("/rhino.png", encoding: none), width: 1cm)
+
 ---
 // Error: 8-21 unknown image format
 #image("./image.typ")
 
+---
+// Error: 8-22 unknown image format
+#image(bytes("hello"))
+
 ---
 // Error: 8-18 failed to parse svg: found closing tag 'g' instead of 'style' in line 4
 #image("/bad.svg")